@@ -0,0 +1,175 @@
+//! Opt-in allocation counting.
+//!
+//! Building with `--features count-allocs` installs [`CountingAllocator`] as the
+//! global allocator of every binary linking the library, and the [`crate::runner`]
+//! then reports the allocations of each part next to its timing:
+//!
+//! ```sh
+//! cargo run --release --features count-allocs --bin day-6
+//! ```
+//!
+//! The counters are process-wide and [`measure`] resets the peak when it
+//! starts, so measurements only hold while one part runs at a time. The `aoc`
+//! binary refuses `run --parallel` when counting is enabled.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Wraps the [`System`] allocator, counting allocations and tracking the peak
+/// of live heap memory.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn grow(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+    fn shrink(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // counted as freeing the old block and allocating the new one
+            Self::shrink(layout.size());
+            Self::grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations made while running a closure with [`measure`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: usize,
+    /// Total bytes requested.
+    pub bytes: usize,
+    /// Highest amount of live heap memory above what was live when the
+    /// measurement started.
+    pub peak: usize,
+}
+
+/// Whether the library was built with the `count-allocs` feature.
+pub fn enabled() -> bool {
+    cfg!(feature = "count-allocs")
+}
+
+/// Runs `f` and returns the allocations it made, or `None` if counting is not
+/// enabled. The counters are global, so allocations of other threads running
+/// at the same time are included, and a concurrent `measure` skews the peak.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    let res = f();
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(current),
+    };
+    (res, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// The counters are global, tests touching them take turns.
+    static COUNTERS: Mutex<()> = Mutex::new(());
+
+    #[cfg(not(feature = "count-allocs"))]
+    fn counters() -> [usize; 3] {
+        [&ALLOCATIONS, &BYTES, &CURRENT].map(|counter| counter.load(Ordering::Relaxed))
+    }
+
+    // with the feature every allocation of every test thread is counted
+    #[cfg(not(feature = "count-allocs"))]
+    #[test]
+    fn instance_counts_its_allocations() {
+        let _lock = COUNTERS.lock().unwrap();
+        let [allocations, bytes, current] = counters();
+        PEAK.store(current, Ordering::Relaxed);
+
+        let allocator = CountingAllocator;
+        let big = Layout::from_size_align(100, 8).unwrap();
+        let small = Layout::from_size_align(50, 8).unwrap();
+        unsafe {
+            let a = allocator.alloc(big);
+            let b = allocator.alloc_zeroed(small);
+            allocator.dealloc(a, big);
+            let b = allocator.realloc(b, small, 70);
+            assert_eq!(counters(), [allocations + 3, bytes + 220, current + 70]);
+            allocator.dealloc(b, Layout::from_size_align(70, 8).unwrap());
+        }
+        assert_eq!(counters(), [allocations + 3, bytes + 220, current]);
+        assert_eq!(PEAK.load(Ordering::Relaxed), current + 150);
+    }
+
+    #[cfg(not(feature = "count-allocs"))]
+    #[test]
+    fn measure_is_off_without_the_feature() {
+        assert!(!enabled());
+        assert_eq!(measure(|| vec![0u8; 64].len()), (64, None));
+    }
+
+    #[cfg(feature = "count-allocs")]
+    #[test]
+    fn measure_resets_between_calls() {
+        let _lock = COUNTERS.lock().unwrap();
+        let (_, first) = measure(|| std::hint::black_box(vec![0u8; 1 << 20]).len());
+        let first = first.unwrap();
+        assert!(first.allocations >= 1);
+        assert!(first.bytes >= 1 << 20);
+        assert!(first.peak >= 1 << 20);
+
+        let (_, second) = measure(|| std::hint::black_box(1 + 1));
+        let second = second.unwrap();
+        assert!(second.bytes < 1 << 20);
+        assert!(second.peak < 1 << 20);
+    }
+}
@@ -0,0 +1,323 @@
+//! The answer database of a year, `inputs/<year>/answers.txt`.
+//!
+//! One answer per line, `=` for the accepted answer and `!=` for a guess that
+//! was rejected. A guess rejected as too high is written as `<`, since the answer
+//! is less than it, and one rejected as too low as `>`:
+//!
+//! ```text
+//! day-7 part_1 = 3749
+//! day-7 part_2 != 1234
+//! day-7 part_2 < 99999
+//! ```
+//!
+//! Empty lines and lines starting with `#` are ignored.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context};
+
+use crate::{
+    input,
+    solution::{Answer, Part},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct AnswerDb {
+    correct: BTreeMap<(u8, Part), Answer>,
+    wrong: BTreeMap<(u8, Part), Vec<Answer>>,
+    /// The smallest guess that was too high.
+    below: BTreeMap<(u8, Part), i128>,
+    /// The largest guess that was too low.
+    above: BTreeMap<(u8, Part), i128>,
+}
+
+/// How an answer relates to the accepted one, the operator of a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    /// `=`, the accepted answer.
+    Correct,
+    /// `!=`, rejected without a hint.
+    Wrong,
+    /// `<`, the accepted answer is lower.
+    TooHigh,
+    /// `>`, the accepted answer is higher.
+    TooLow,
+}
+
+impl Relation {
+    fn operator(self) -> &'static str {
+        match self {
+            Relation::Correct => "=",
+            Relation::Wrong => "!=",
+            Relation::TooHigh => "<",
+            Relation::TooLow => ">",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// The database has a different accepted answer.
+    Wrong {
+        expected: Answer,
+    },
+    /// The answer was already rejected once.
+    KnownWrong,
+    /// Nothing is known about this part yet.
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong { expected } => write!(f, "wrong, expected {expected}"),
+            Verdict::KnownWrong => write!(f, "known wrong"),
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+pub fn path(year: u16) -> PathBuf {
+    input::year_dir(year).join("answers.txt")
+}
+
+impl AnswerDb {
+    /// Loads the database of `year` from [`path`], or an empty one if it does
+    /// not exist.
+    pub fn load(year: u16) -> anyhow::Result<Self> {
+        Self::load_from(&path(year))
+    }
+
+    pub fn load_from(path: &Path) -> anyhow::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => {
+                Self::parse(&contents).with_context(|| format!("In {}", path.display()))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Could not read {}", path.display())),
+        }
+    }
+
+    pub fn parse(contents: &str) -> anyhow::Result<Self> {
+        let mut db = Self::default();
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (day, part, relation, answer) =
+                parse_line(line).with_context(|| format!("Invalid answer on line {}", i + 1))?;
+            db.insert(day, part, relation, answer)
+                .with_context(|| format!("Invalid answer on line {}", i + 1))?;
+        }
+        Ok(db)
+    }
+
+    fn insert(
+        &mut self,
+        day: u8,
+        part: Part,
+        relation: Relation,
+        answer: Answer,
+    ) -> anyhow::Result<()> {
+        let key = (day, part);
+        let number = || {
+            answer
+                .0
+                .parse::<i128>()
+                .with_context(|| format!("`{}` needs a number", relation.operator()))
+        };
+        match relation {
+            Relation::Correct => {
+                self.correct.insert(key, answer);
+            }
+            Relation::Wrong => self.wrong.entry(key).or_default().push(answer),
+            Relation::TooHigh => {
+                let bound = self.below.entry(key).or_insert(i128::MAX);
+                *bound = (*bound).min(number()?);
+            }
+            Relation::TooLow => {
+                let bound = self.above.entry(key).or_insert(i128::MIN);
+                *bound = (*bound).max(number()?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds an answer to the database and appends it to the file at `path`,
+    /// creating its directory if needed.
+    pub fn record(
+        &mut self,
+        path: &Path,
+        day: u8,
+        part: Part,
+        relation: Relation,
+        answer: &Answer,
+    ) -> anyhow::Result<()> {
+        self.insert(day, part, relation, answer.clone())?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Could not create {}", dir.display()))?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Could not open {}", path.display()))?;
+        writeln!(file, "day-{day} {part} {} {answer}", relation.operator())
+            .with_context(|| format!("Could not write {}", path.display()))
+    }
+
+    pub fn correct(&self, day: u8, part: Part) -> Option<&Answer> {
+        self.correct.get(&(day, part))
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Verdict {
+        match self.correct(day, part) {
+            Some(expected) if expected == answer => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.clone(),
+            },
+            None if self.rejected(day, part, answer) => Verdict::KnownWrong,
+            None => Verdict::Unknown,
+        }
+    }
+
+    /// Whether `answer` was rejected, or is out of the bounds set by too high and
+    /// too low guesses.
+    fn rejected(&self, day: u8, part: Part, answer: &Answer) -> bool {
+        let key = (day, part);
+        let number = answer.0.parse::<i128>().ok();
+        self.wrong
+            .get(&key)
+            .is_some_and(|wrong| wrong.contains(answer))
+            || number
+                .zip(self.below.get(&key))
+                .is_some_and(|(n, &below)| n >= below)
+            || number
+                .zip(self.above.get(&key))
+                .is_some_and(|(n, &above)| n <= above)
+    }
+}
+
+fn parse_line(line: &str) -> anyhow::Result<(u8, Part, Relation, Answer)> {
+    let mut words = line.split_whitespace();
+    let (Some(day), Some(part), Some(op), Some(answer), None) = (
+        words.next(),
+        words.next(),
+        words.next(),
+        words.next(),
+        words.next(),
+    ) else {
+        bail!("Expected `day-N part_P = ANSWER`");
+    };
+    let day = day
+        .strip_prefix("day-")
+        .and_then(|day| day.parse().ok())
+        .context("Expected `day-N`")?;
+    let part = match part {
+        "part_1" => Part::One,
+        "part_2" => Part::Two,
+        _ => bail!("Expected `part_1` or `part_2`"),
+    };
+    let relation = match op {
+        "=" => Relation::Correct,
+        "!=" => Relation::Wrong,
+        "<" => Relation::TooHigh,
+        ">" => Relation::TooLow,
+        _ => bail!("Expected `=`, `!=`, `<` or `>`"),
+    };
+    Ok((day, part, relation, Answer(answer.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_dir;
+
+    fn answer(answer: &str) -> Answer {
+        Answer(answer.to_string())
+    }
+
+    #[test]
+    fn verdicts() {
+        let db = AnswerDb::parse(
+            "# day 7\n\nday-7 part_1 = 3749\nday-7 part_2 != 1234\nday-7 part_2 < 9000\nday-7 part_2 > 100\n",
+        )
+        .unwrap();
+        let check = |part, guess| db.check(7, part, &answer(guess));
+        assert_eq!(check(Part::One, "3749"), Verdict::Correct);
+        assert_eq!(
+            check(Part::One, "3750"),
+            Verdict::Wrong {
+                expected: answer("3749")
+            }
+        );
+        assert_eq!(check(Part::Two, "1234"), Verdict::KnownWrong);
+        assert_eq!(check(Part::Two, "9000"), Verdict::KnownWrong);
+        assert_eq!(check(Part::Two, "100"), Verdict::KnownWrong);
+        assert_eq!(check(Part::Two, "4321"), Verdict::Unknown);
+        assert_eq!(db.check(8, Part::One, &answer("1")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let error = |contents| format!("{:#}", AnswerDb::parse(contents).unwrap_err());
+        assert_eq!(
+            error("day-1 part_1 = 1\nday-1 part_3 = 2\n"),
+            "Invalid answer on line 2: Expected `part_1` or `part_2`"
+        );
+        assert_eq!(
+            error("day-1 part_1 < many\n"),
+            "Invalid answer on line 1: `<` needs a number: invalid digit found in string"
+        );
+        assert_eq!(
+            error("day-1 part_1 =\n"),
+            "Invalid answer on line 1: Expected `day-N part_P = ANSWER`"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = temp_dir("answers").join("answers.txt");
+        assert!(AnswerDb::load_from(&path)
+            .unwrap()
+            .correct(1, Part::One)
+            .is_none());
+        let mut db = AnswerDb::default();
+        let records = [
+            (Part::One, Relation::Correct, "11"),
+            (Part::Two, Relation::Wrong, "30"),
+            (Part::Two, Relation::TooHigh, "40"),
+            (Part::Two, Relation::TooLow, "20"),
+            (Part::Two, Relation::Correct, "31"),
+        ];
+        for (part, relation, guess) in records {
+            db.record(&path, 1, part, relation, &answer(guess)).unwrap();
+        }
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "day-1 part_1 = 11\nday-1 part_2 != 30\nday-1 part_2 < 40\nday-1 part_2 > 20\nday-1 part_2 = 31\n"
+        );
+        let loaded = AnswerDb::load_from(&path).unwrap();
+        assert_eq!(loaded.correct(1, Part::One), Some(&answer("11")));
+        assert_eq!(loaded.correct(1, Part::Two), Some(&answer("31")));
+        for guess in ["30", "40", "20"] {
+            let expected = Verdict::Wrong {
+                expected: answer("31"),
+            };
+            assert_eq!(loaded.check(1, Part::Two, &answer(guess)), expected);
+        }
+        assert!(loaded.rejected(1, Part::Two, &answer("30")));
+        assert!(loaded.rejected(1, Part::Two, &answer("45")));
+        assert!(loaded.rejected(1, Part::Two, &answer("15")));
+        assert!(!loaded.rejected(1, Part::Two, &answer("35")));
+    }
+}
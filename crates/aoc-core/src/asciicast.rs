@@ -0,0 +1,191 @@
+//! `aoc record <DAY>`: a [`Scene`] as an [asciicast v2] file, to replay with
+//! `asciinema play` or embed with asciinema-player.
+//!
+//! Every frame redraws the whole grid with the highlights in colour, followed
+//! by the label of its step. Long simulations are sampled down: [`CastOptions`]
+//! sets the frame rate, keeps one step in `every` and caps the frame count.
+//! The last step always gets a frame.
+//!
+//! [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+
+use std::io::Write;
+
+use anyhow::bail;
+use serde::Serialize;
+
+use crate::scene::{Highlight, Scene};
+
+#[derive(Debug, Clone)]
+pub struct CastOptions {
+    /// Frames per second.
+    pub fps: f64,
+    /// Keep one step in `every`.
+    pub every: usize,
+    /// Keep fewer steps when there would be more frames than this. 1 keeps
+    /// only the last step, and so does 0.
+    pub max_frames: Option<usize>,
+    pub title: Option<String>,
+}
+
+impl Default for CastOptions {
+    fn default() -> Self {
+        Self {
+            fps: 10.0,
+            every: 1,
+            max_frames: None,
+            title: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Header<'a> {
+    version: u8,
+    width: usize,
+    height: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<&'a str>,
+}
+
+/// The steps that get a frame out of `steps`.
+pub fn sample(steps: usize, options: &CastOptions) -> Vec<usize> {
+    let Some(last) = steps.checked_sub(1) else {
+        return vec![];
+    };
+    let mut every = options.every.max(1);
+    if let Some(max) = options.max_frames {
+        if max <= 1 {
+            return vec![last];
+        }
+        // the last step takes a frame of its own
+        every = every.max(last.div_ceil(max - 1));
+    }
+    let mut sampled: Vec<usize> = (0..=last).step_by(every).collect();
+    if sampled.last() != Some(&last) {
+        sampled.push(last);
+    }
+    sampled
+}
+
+/// Writes `scene` as an asciicast, returning the number of frames.
+pub fn write(scene: &Scene, options: &CastOptions, mut w: impl Write) -> anyhow::Result<usize> {
+    if !(options.fps > 0.0 && options.fps.is_finite()) {
+        bail!("The frame rate must be positive, not {}", options.fps);
+    }
+    let steps = sample(scene.steps().len(), options);
+    let labels = steps
+        .iter()
+        .map(|&step| scene.steps()[step].label.chars().count());
+    let header = Header {
+        version: 2,
+        width: labels.max().unwrap_or(0).max(scene.columns()),
+        // a blank line and the label under the grid
+        height: scene.rows() + 2,
+        title: options.title.as_deref(),
+    };
+    writeln!(w, "{}", serde_json::to_string(&header)?)?;
+    for (frame, &step) in steps.iter().enumerate() {
+        // clear the screen once, then redraw over the previous frame
+        let mut data = String::from(if frame == 0 {
+            "\x1b[2J\x1b[H"
+        } else {
+            "\x1b[H"
+        });
+        for row in scene.render(step, painter()) {
+            data += &row;
+            data += "\x1b[0m\x1b[K\r\n";
+        }
+        data += "\x1b[K\r\n";
+        data += &scene.steps()[step].label;
+        data += "\x1b[K";
+        let time = frame as f64 / options.fps;
+        writeln!(w, "{}", serde_json::to_string(&(time, "o", data))?)?;
+    }
+    Ok(steps.len())
+}
+
+/// Paints cells in the ANSI colours of their highlight, only switching colours
+/// between cells that differ to keep the frames small.
+fn painter() -> impl FnMut(&mut String, char, Option<Highlight>) {
+    let mut current = None;
+    move |line, glyph, highlight| {
+        if line.is_empty() || current != Some(highlight) {
+            line.push_str(match highlight {
+                None => "\x1b[0m",
+                Some(Highlight::Visited) => "\x1b[0;44m",
+                Some(Highlight::Frontier) => "\x1b[0;30;46m",
+                Some(Highlight::Current) => "\x1b[0;1;30;43m",
+            });
+            current = Some(highlight);
+        }
+        line.push(glyph);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+    use crate::{scene::Tile, Grid};
+
+    fn options(every: usize, max_frames: Option<usize>) -> CastOptions {
+        CastOptions {
+            every,
+            max_frames,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sampling_keeps_the_last_step() {
+        assert_eq!(sample(0, &options(1, None)), [0; 0]);
+        assert_eq!(sample(1, &options(3, None)), [0]);
+        assert_eq!(sample(5, &options(1, None)), [0, 1, 2, 3, 4]);
+        assert_eq!(sample(6, &options(2, None)), [0, 2, 4, 5]);
+        assert_eq!(sample(101, &options(1, Some(5))), [0, 25, 50, 75, 100]);
+        assert_eq!(sample(10, &options(0, Some(1))), [9]);
+        assert_eq!(sample(10, &options(1, Some(2))), [0, 9]);
+    }
+
+    #[test]
+    fn frames() {
+        let mut scene = Scene::new(Grid(vec![vec![Tile::new('.', ""); 2]]));
+        scene.push("a long label").mark((0, 1), Highlight::Current);
+        scene.push("b");
+        let options = CastOptions {
+            fps: 4.0,
+            title: Some("walk".to_string()),
+            ..Default::default()
+        };
+        let mut out = vec![];
+        assert_eq!(write(&scene, &options, &mut out).unwrap(), 2);
+        let lines: Vec<Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            lines[0],
+            json!({"version": 2, "width": 12, "height": 3, "title": "walk"})
+        );
+        assert_eq!(
+            lines[1],
+            json!([0.0, "o", "\x1b[2J\x1b[H\x1b[0m.\x1b[0;1;30;43m.\x1b[0m\x1b[K\r\n\x1b[K\r\na long label\x1b[K"])
+        );
+        assert_eq!(lines[2][0], json!(0.25));
+        assert!(lines[2][2].as_str().unwrap().starts_with("\x1b[H\x1b[0m.."));
+    }
+
+    #[test]
+    fn frame_rate_must_be_positive() {
+        let scene = Scene::new(Grid(vec![]));
+        for fps in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let options = CastOptions {
+                fps,
+                ..Default::default()
+            };
+            assert!(write(&scene, &options, vec![]).is_err());
+        }
+    }
+}
@@ -0,0 +1,356 @@
+//! `aoc explore <DAY>`: an interactive terminal view of a [`Scene`].
+//!
+//! The arrow keys or `hjkl` move the cursor (`HJKL` by ten cells) and the view
+//! follows it, `+` and `-` zoom. `n` and `p` step forward and backward through
+//! the recorded states (`N` and `P` by ten, `g` and `G` to the first and the
+//! last), `f` toggles whether the cursor follows the solver, `q` quits. The
+//! status bar shows the step and the tile under the cursor.
+
+use std::{cmp::Reverse, collections::HashMap};
+
+use anyhow::bail;
+use ratatui::{
+    buffer::Buffer,
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::Paragraph,
+    DefaultTerminal, Frame,
+};
+
+use crate::scene::{Highlight, Mark, Scene};
+
+/// Zoomed out, a character covers `2^-zoom` cells in each direction. Zoomed
+/// in, a cell takes `2 * zoom` columns and `zoom` rows, to look square.
+const ZOOM: std::ops::RangeInclusive<i32> = -3..=3;
+
+const HELP: &str =
+    "hjkl move  HJKL x10  +/- zoom  n/p step  N/P x10  g/G first/last  f follow  q quit";
+
+struct Explorer<'a> {
+    scene: &'a Scene,
+    step: usize,
+    /// `(y, x)` of the cell under the cursor.
+    cursor: (usize, usize),
+    /// `(y, x)` of the top left cell in view.
+    offset: (usize, usize),
+    zoom: i32,
+    /// Move the cursor to the solver on every step.
+    follow: bool,
+}
+
+/// Shows `scene` until the user quits.
+pub fn explore(scene: &Scene) -> anyhow::Result<()> {
+    if scene.rows() == 0 || scene.columns() == 0 {
+        bail!("The grid is empty");
+    }
+    let mut explorer = Explorer::new(scene);
+    let mut terminal = ratatui::init();
+    let result = explorer.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl<'a> Explorer<'a> {
+    /// At the first step, following the solver.
+    fn new(scene: &'a Scene) -> Self {
+        let mut explorer = Explorer {
+            scene,
+            step: 0,
+            cursor: (0, 0),
+            offset: (0, 0),
+            zoom: 0,
+            follow: true,
+        };
+        explorer.go_to(0);
+        explorer
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> anyhow::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind == KeyEventKind::Press && !self.press(key) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies a key press, returning `false` when it quits.
+    fn press(&mut self, key: KeyEvent) -> bool {
+        let block = self.block() as isize;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Left | KeyCode::Char('h') => self.move_cursor(0, -block),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(block, 0),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-block, 0),
+            KeyCode::Right | KeyCode::Char('l') => self.move_cursor(0, block),
+            KeyCode::Char('H') => self.move_cursor(0, -10 * block),
+            KeyCode::Char('J') => self.move_cursor(10 * block, 0),
+            KeyCode::Char('K') => self.move_cursor(-10 * block, 0),
+            KeyCode::Char('L') => self.move_cursor(0, 10 * block),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.zoom = (self.zoom + 1).min(*ZOOM.end()),
+            KeyCode::Char('-') => self.zoom = (self.zoom - 1).max(*ZOOM.start()),
+            KeyCode::Char('n') | KeyCode::Char(' ') => self.go_to(self.step.saturating_add(1)),
+            KeyCode::Char('p') => self.go_to(self.step.saturating_sub(1)),
+            KeyCode::Char('N') => self.go_to(self.step.saturating_add(10)),
+            KeyCode::Char('P') => self.go_to(self.step.saturating_sub(10)),
+            KeyCode::Char('g') => self.go_to(0),
+            KeyCode::Char('G') => self.go_to(usize::MAX),
+            KeyCode::Char('f') => {
+                self.follow = !self.follow;
+                self.go_to(self.step);
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// Goes to `step`, or the last step when there are fewer.
+    fn go_to(&mut self, step: usize) {
+        self.step = step.min(self.scene.steps().len().saturating_sub(1));
+        let current = self.scene.steps().get(self.step).and_then(|step| {
+            step.marks
+                .iter()
+                .find(|mark| mark.highlight == Highlight::Current)
+        });
+        if let (true, Some(mark)) = (self.follow, current) {
+            self.cursor = mark.pos;
+        }
+    }
+
+    fn move_cursor(&mut self, dy: isize, dx: isize) {
+        let clamp = |pos: usize, delta: isize, len: usize| {
+            pos.saturating_add_signed(delta).min(len.saturating_sub(1))
+        };
+        self.cursor = (
+            clamp(self.cursor.0, dy, self.scene.rows()),
+            clamp(self.cursor.1, dx, self.scene.columns()),
+        );
+    }
+
+    /// Cells covered by a character in each direction.
+    fn block(&self) -> usize {
+        1 << (-self.zoom).max(0)
+    }
+
+    /// Characters taken by a cell, `(columns, rows)`.
+    fn cell_size(&self) -> (usize, usize) {
+        match self.zoom {
+            ..=0 => (1, 1),
+            zoom => (2 * zoom as usize, zoom as usize),
+        }
+    }
+
+    /// The number of `(rows, columns)` of cells that fit in `area`.
+    fn view(&self, area: Rect) -> (usize, usize) {
+        let (width, height) = self.cell_size();
+        let block = self.block();
+        (
+            (area.height as usize / height * block).max(1),
+            (area.width as usize / width * block).max(1),
+        )
+    }
+
+    /// Pans the least needed to keep the cursor in view.
+    fn scroll(&mut self, area: Rect) {
+        let (rows, columns) = self.view(area);
+        let scroll = |offset: usize, cursor: usize, len: usize| {
+            offset.clamp(cursor.saturating_sub(len - 1), cursor)
+        };
+        self.offset = (
+            scroll(self.offset.0, self.cursor.0, rows),
+            scroll(self.offset.1, self.cursor.1, columns),
+        );
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [grid, status] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(3)]).areas(frame.area());
+        self.scroll(grid);
+        let overlay = self.scene.overlay(self.step);
+        self.draw_grid(&overlay, grid, frame.buffer_mut());
+        frame.render_widget(self.status(&overlay), status);
+    }
+
+    fn draw_grid(&self, overlay: &HashMap<(usize, usize), Mark>, area: Rect, buf: &mut Buffer) {
+        let (width, height) = self.cell_size();
+        let block = self.block();
+        let tiles = &self.scene.tiles().0;
+        for sy in 0..area.height {
+            for sx in 0..area.width {
+                let y = self.offset.0 + sy as usize / height * block;
+                let x = self.offset.1 + sx as usize / width * block;
+                // zoomed out, the character shows the most important cell it covers
+                let cells = (y..y + block).flat_map(|y| (x..x + block).map(move |x| (y, x)));
+                let Some((pos, tile)) = cells
+                    .filter_map(|pos| Some((pos, tiles.get(pos.0)?.get(pos.1)?)))
+                    .min_by_key(|(pos, _)| Reverse(overlay.get(pos).map(|mark| mark.highlight)))
+                else {
+                    continue;
+                };
+                let mark = overlay.get(&pos);
+                let centre =
+                    sy as usize % height == height / 2 && sx as usize % width == (width - 1) / 2;
+                let glyph = match centre {
+                    true => mark.and_then(|mark| mark.glyph).unwrap_or(tile.glyph),
+                    false => ' ',
+                };
+                let mut style = mark.map_or(Style::new(), |mark| style(mark.highlight));
+                if (y..y + block).contains(&self.cursor.0)
+                    && (x..x + block).contains(&self.cursor.1)
+                {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                if let Some(cell) = buf.cell_mut((area.x + sx, area.y + sy)) {
+                    cell.set_char(glyph).set_style(style);
+                }
+            }
+        }
+    }
+
+    fn status(&self, overlay: &HashMap<(usize, usize), Mark>) -> Paragraph<'static> {
+        let steps = self.scene.steps();
+        let step = match steps.get(self.step) {
+            Some(step) => format!("step {}/{}  {}", self.step + 1, steps.len(), step.label),
+            None => "no steps recorded".to_string(),
+        };
+        let (y, x) = self.cursor;
+        let mut cursor = match self.scene.tiles().get(self.cursor) {
+            Some(tile) => format!("({y}, {x})  {}  {}", tile.glyph, tile.value),
+            None => format!("({y}, {x})  outside the grid"),
+        };
+        if let Some(mark) = overlay.get(&self.cursor) {
+            cursor += &format!("  [{:?}]", mark.highlight);
+        }
+        cursor += &format!(
+            "  zoom {}  follow {}",
+            self.zoom,
+            if self.follow { "on" } else { "off" }
+        );
+        Paragraph::new(vec![
+            Line::from(step),
+            Line::from(cursor),
+            Line::styled(HELP, Style::new().fg(Color::DarkGray)),
+        ])
+    }
+}
+
+fn style(highlight: Highlight) -> Style {
+    match highlight {
+        Highlight::Visited => Style::new().bg(Color::Blue),
+        Highlight::Frontier => Style::new().fg(Color::Black).bg(Color::Cyan),
+        Highlight::Current => Style::new()
+            .fg(Color::Black)
+            .bg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scene::Tile, Grid};
+
+    /// A 3x4 grid where the solver walks along the top row.
+    fn scene() -> Scene {
+        let mut scene = Scene::new(Grid(vec![vec![Tile::new('.', "floor"); 4]; 3]));
+        for x in 0..4 {
+            scene
+                .push(format!("x = {x}"))
+                .mark((0, x), Highlight::Current);
+        }
+        scene
+    }
+
+    fn press(explorer: &mut Explorer, keys: &str) {
+        for c in keys.chars() {
+            assert!(explorer.press(KeyEvent::from(KeyCode::Char(c))), "{c}");
+        }
+    }
+
+    #[test]
+    fn cursor_stays_on_the_grid() {
+        let scene = scene();
+        let mut explorer = Explorer::new(&scene);
+        press(&mut explorer, "kh");
+        assert_eq!(explorer.cursor, (0, 0));
+        press(&mut explorer, "L");
+        assert_eq!(explorer.cursor, (0, 3));
+        press(&mut explorer, "jjjj");
+        assert_eq!(explorer.cursor, (2, 3));
+        // zoomed out a move covers a whole character
+        press(&mut explorer, "-K");
+        assert_eq!(explorer.cursor, (0, 3));
+        press(&mut explorer, "h");
+        assert_eq!(explorer.cursor, (0, 1));
+    }
+
+    #[test]
+    fn zoom_is_clamped() {
+        let scene = scene();
+        let mut explorer = Explorer::new(&scene);
+        press(&mut explorer, "+++++");
+        assert_eq!(explorer.zoom, *ZOOM.end());
+        press(&mut explorer, "----------");
+        assert_eq!(explorer.zoom, *ZOOM.start());
+    }
+
+    #[test]
+    fn view_scrolls_to_the_cursor() {
+        let scene = scene();
+        let mut explorer = Explorer::new(&scene);
+        let area = Rect::new(0, 0, 2, 2);
+        press(&mut explorer, "Lj");
+        explorer.scroll(area);
+        assert_eq!(explorer.offset, (0, 2));
+        // past the edges the view stops with the cursor
+        press(&mut explorer, "JL");
+        explorer.scroll(area);
+        assert_eq!(explorer.offset, (1, 2));
+        press(&mut explorer, "KH");
+        explorer.scroll(area);
+        assert_eq!(explorer.offset, (0, 0));
+    }
+
+    #[test]
+    fn steps_follow_the_solver() {
+        let scene = scene();
+        let mut explorer = Explorer::new(&scene);
+        press(&mut explorer, "n");
+        assert_eq!((explorer.step, explorer.cursor), (1, (0, 1)));
+        press(&mut explorer, "N");
+        assert_eq!((explorer.step, explorer.cursor), (3, (0, 3)));
+        press(&mut explorer, "p");
+        assert_eq!((explorer.step, explorer.cursor), (2, (0, 2)));
+        press(&mut explorer, "g");
+        assert_eq!((explorer.step, explorer.cursor), (0, (0, 0)));
+    }
+
+    #[test]
+    fn follow_toggles() {
+        let scene = scene();
+        let mut explorer = Explorer::new(&scene);
+        press(&mut explorer, "fjG");
+        assert!(!explorer.follow);
+        assert_eq!((explorer.step, explorer.cursor), (3, (1, 0)));
+        // turning it back on jumps to the solver
+        press(&mut explorer, "f");
+        assert!(explorer.follow);
+        assert_eq!(explorer.cursor, (0, 3));
+    }
+
+    #[test]
+    fn quit_keys() {
+        let scene = scene();
+        let mut explorer = Explorer::new(&scene);
+        assert!(!explorer.press(KeyEvent::from(KeyCode::Char('q'))));
+        assert!(!explorer.press(KeyEvent::from(KeyCode::Esc)));
+        assert!(!explorer.press(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+        assert!(explorer.press(KeyEvent::from(KeyCode::Char('c'))));
+    }
+}
@@ -0,0 +1,192 @@
+//! `aoc generate <DAY>`: random puzzle inputs from a seed.
+//!
+//! Days write their inputs in [`Solution::generate`](crate::Solution::generate)
+//! with an [`Rng`], the same seed and size always give the same input. What
+//! the size counts is up to the day: lines, reports, grid sides, equations.
+
+use anyhow::Context;
+
+use crate::solution::Solver;
+
+/// A small seeded random number generator (SplitMix64), good enough for
+/// puzzle inputs and stable across versions, unlike a dependency's.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..n`, `n` must not be 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "empty range");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// A number in `low..=high`.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low <= high, "empty range");
+        match high - low {
+            u64::MAX => self.next_u64(),
+            span => low + ((self.next_u64() as u128 * (span as u128 + 1)) >> 64) as u64,
+        }
+    }
+
+    /// `true` with probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        unit < p
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// A random input for `solver`, checked to parse. A `size` of 0 counts as 1.
+pub fn generate(solver: &dyn Solver, seed: u64, size: usize) -> anyhow::Result<String> {
+    let size = size.max(1);
+    let input = solver
+        .generate(&mut Rng::new(seed), size)
+        .with_context(|| format!("Day {} has no input generator", solver.day()))?;
+    solver.parse(&input).with_context(|| {
+        format!(
+            "The generated input of day {} does not parse (seed {seed}, size {size})",
+            solver.day()
+        )
+    })?;
+    Ok(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cancel::Cancel,
+        solution::{Answer, Solution},
+    };
+
+    /// Generates `size` numbers, one per line, or a stray word for size 2.
+    struct Numbers;
+
+    impl Solution for Numbers {
+        const YEAR: u16 = 2024;
+        const DAY: u8 = 1;
+        type Input = Vec<u64>;
+
+        fn parse(input: &str) -> anyhow::Result<Self::Input> {
+            Ok(input
+                .lines()
+                .map(|line| line.parse())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part_1(input: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+            Ok(input.len())
+        }
+
+        fn part_2(input: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+            Ok(input.len())
+        }
+
+        fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+            match size {
+                2 => Some("two\n".to_string()),
+                size => Some(
+                    (0..size)
+                        .map(|_| format!("{}\n", rng.between(0, 9)))
+                        .collect(),
+                ),
+            }
+        }
+    }
+
+    /// Has no generator.
+    struct Silent;
+
+    impl Solution for Silent {
+        const YEAR: u16 = 2024;
+        const DAY: u8 = 2;
+        type Input = ();
+
+        fn parse(_: &str) -> anyhow::Result<Self::Input> {
+            Ok(())
+        }
+
+        fn part_1(_: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+            Ok(0)
+        }
+
+        fn part_2(_: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn splitmix_reference_values() {
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 0xe220a8397b1dcdaf);
+        assert_eq!(rng.next_u64(), 0x6e789e6aa1b965f4);
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!(rng.below(3) < 3);
+            assert!((5..=6).contains(&rng.between(5, 6)));
+        }
+        assert_eq!(rng.between(4, 4), 4);
+        rng.between(0, u64::MAX);
+        assert!(!rng.chance(0.0));
+        assert!(rng.chance(1.0));
+    }
+
+    #[test]
+    fn shuffle_is_a_seeded_permutation() {
+        let shuffled = |seed| {
+            let mut items: Vec<u32> = (0..20).collect();
+            Rng::new(seed).shuffle(&mut items);
+            items
+        };
+        let mut sorted = shuffled(1);
+        assert_eq!(shuffled(1), sorted);
+        assert_ne!(shuffled(2), sorted);
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn generated_inputs_are_checked() {
+        let input = generate(&Numbers, 3, 0).unwrap();
+        assert_eq!(input.lines().count(), 1);
+        assert_eq!(
+            generate(&Numbers, 3, 5).unwrap(),
+            generate(&Numbers, 3, 5).unwrap()
+        );
+        let error = generate(&Numbers, 3, 2).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            "The generated input of day 1 does not parse (seed 3, size 2)"
+        );
+        let error = generate(&Silent, 3, 1).unwrap_err().to_string();
+        assert_eq!(error, "Day 2 has no input generator");
+    }
+}
@@ -0,0 +1,357 @@
+//! Grids as PPM or PNG images, for inputs too large to read in a terminal.
+//!
+//! An [`Image`] has one pixel per cell, coloured by a function of the cell or
+//! by [`heat`] for numeric grids. Layers of highlighted cells are blended on
+//! top with [`Image::overlay`], and [`Image::scaled`] blows cells up to
+//! squares before saving. [`save_sequence`] writes numbered frames, for
+//! instance the steps of a [`Scene`] through [`Image::from_scene`].
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{bail, Context};
+
+use crate::{
+    scene::{Highlight, Scene, Tile},
+    Grid,
+};
+
+pub type Rgb = [u8; 3];
+
+/// Drawn where a row is shorter than the longest one.
+const BACKGROUND: Rgb = [0, 0, 0];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+}
+
+impl FromStr for ImageFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(Self::Ppm),
+            "png" => Ok(Self::Png),
+            _ => bail!("Unknown image format `{s}`, expected ppm or png"),
+        }
+    }
+}
+
+impl ImageFormat {
+    /// The format of a path ending in `.ppm` or `.png`.
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        let extension = path.extension().and_then(|ext| ext.to_str());
+        extension
+            .and_then(|ext| ext.parse().ok())
+            .with_context(|| format!("{} is not a .ppm or .png file", path.display()))
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Ppm => "ppm",
+            Self::Png => "png",
+        }
+    }
+}
+
+impl Image {
+    /// One pixel per cell of `grid`, coloured by `colour`.
+    pub fn from_grid<T>(grid: &Grid<T>, mut colour: impl FnMut(&T) -> Rgb) -> Self {
+        let width = grid.0.iter().map(Vec::len).max().unwrap_or(0);
+        let height = grid.0.len();
+        let mut pixels = vec![BACKGROUND; width * height];
+        for ((y, x), cell) in grid.indexed_iter() {
+            pixels[y * width + x] = colour(cell);
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// A numeric grid on the [`heat`] palette, from its lowest value to its
+    /// highest.
+    pub fn height_map<T: Copy + Into<f64>>(grid: &Grid<T>) -> Self {
+        let (min, max) =
+            grid.iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                    let value = value.into();
+                    (min.min(value), max.max(value))
+                });
+        Self::from_grid(grid, |&value| heat(value.into(), min, max))
+    }
+
+    /// `step` of `scene`: the tiles coloured by [`glyph_colour`] with the
+    /// highlights on top.
+    pub fn from_scene(scene: &Scene, step: usize) -> Self {
+        let mut image = Self::from_grid(scene.tiles(), |tile: &Tile| glyph_colour(tile.glyph));
+        for (pos, mark) in scene.overlay(step) {
+            if let Some(glyph) = mark.glyph {
+                image.set(pos, glyph_colour(glyph));
+            }
+            let (colour, alpha) = match mark.highlight {
+                Highlight::Visited => ([60, 110, 220], 0.6),
+                Highlight::Frontier => ([60, 220, 220], 0.8),
+                Highlight::Current => ([250, 210, 40], 1.0),
+            };
+            image.overlay([pos], colour, alpha);
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (y, x): (usize, usize)) -> Option<Rgb> {
+        if x >= self.width {
+            return None;
+        }
+        self.pixels.get(y * self.width + x).copied()
+    }
+
+    /// Colours the pixel at `(y, x)`, ignoring positions outside the image.
+    pub fn set(&mut self, (y, x): (usize, usize), colour: Rgb) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = colour;
+        }
+    }
+
+    /// Blends `colour` over `cells`, `alpha` 1 covers them completely.
+    pub fn overlay(
+        &mut self,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+        colour: Rgb,
+        alpha: f64,
+    ) -> &mut Self {
+        let alpha = alpha.clamp(0.0, 1.0);
+        for pos in cells {
+            if let Some(below) = self.get(pos) {
+                let blend = |i: usize| {
+                    (below[i] as f64 * (1.0 - alpha) + colour[i] as f64 * alpha).round() as u8
+                };
+                self.set(pos, [blend(0), blend(1), blend(2)]);
+            }
+        }
+        self
+    }
+
+    /// Every pixel as a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let factor = factor.max(1);
+        let width = self.width * factor;
+        let pixels = (0..self.height * factor)
+            .flat_map(|y| (0..width).map(move |x| (y / factor, x / factor)))
+            .map(|(y, x)| self.pixels[y * self.width + x])
+            .collect();
+        Self {
+            width,
+            height: self.height * factor,
+            pixels,
+        }
+    }
+
+    /// Writes a binary PPM (P6).
+    pub fn write_ppm(&self, mut w: impl Write) -> anyhow::Result<()> {
+        write!(w, "P6\n{} {}\n255\n", self.width, self.height)?;
+        w.write_all(self.pixels.as_flattened())?;
+        Ok(())
+    }
+
+    pub fn write_png(&self, w: impl Write) -> anyhow::Result<()> {
+        let mut encoder = png::Encoder::new(w, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(self.pixels.as_flattened())?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Saves the image as a PPM or a PNG, depending on the extension of `path`.
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let format = ImageFormat::from_path(path)?;
+        let file =
+            File::create(path).with_context(|| format!("Could not create {}", path.display()))?;
+        let w = BufWriter::new(file);
+        match format {
+            ImageFormat::Ppm => self.write_ppm(w),
+            ImageFormat::Png => self.write_png(w),
+        }
+        .with_context(|| format!("Could not write {}", path.display()))
+    }
+}
+
+/// Saves `frames` in `dir` as `frame-00000.png` and so on, returning their
+/// paths.
+pub fn save_sequence(
+    dir: &Path,
+    format: ImageFormat,
+    frames: impl IntoIterator<Item = Image>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Could not create {}", dir.display()))?;
+    frames
+        .into_iter()
+        .enumerate()
+        .map(|(i, frame)| {
+            let path = dir.join(format!("frame-{i:05}.{}", format.extension()));
+            frame.save(&path)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+/// `value` between `min` and `max` on a dark blue, green, yellow palette.
+pub fn heat(value: f64, min: f64, max: f64) -> Rgb {
+    const STOPS: [Rgb; 5] = [
+        [68, 1, 84],
+        [59, 82, 139],
+        [33, 145, 140],
+        [94, 201, 98],
+        [253, 231, 37],
+    ];
+    let t = match max > min {
+        true => ((value - min) / (max - min)).clamp(0.0, 1.0),
+        false => 0.0,
+    };
+    let scaled = t * (STOPS.len() - 1) as f64;
+    let i = (scaled.floor() as usize).min(STOPS.len() - 2);
+    let t = scaled - i as f64;
+    let mix =
+        |c: usize| (STOPS[i][c] as f64 * (1.0 - t) + STOPS[i + 1][c] as f64 * t).round() as u8;
+    [mix(0), mix(1), mix(2)]
+}
+
+/// A colour for the glyph of a tile: dark for `.`, grey for walls, [`heat`]
+/// for digits and a fixed colour picked from the character for anything else.
+pub fn glyph_colour(glyph: char) -> Rgb {
+    match glyph {
+        '.' | ' ' => [20, 20, 28],
+        '#' => [150, 150, 150],
+        '0'..='9' => heat(glyph as u32 as f64, '0' as u32 as f64, '9' as u32 as f64),
+        _ => {
+            let hash = (glyph as u32).wrapping_mul(2654435761);
+            let channel = |shift: u32| 96 + ((hash >> shift) & 0x9f) as u8;
+            [channel(0), channel(8), channel(16)]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::temp_dir;
+
+    const RED: Rgb = [255, 0, 0];
+    const WHITE: Rgb = [255, 255, 255];
+
+    /// A 2x2 image, red on the diagonal.
+    fn diagonal() -> Image {
+        let grid = Grid(vec![vec![true, false], vec![false, true]]);
+        Image::from_grid(&grid, |&on| if on { RED } else { WHITE })
+    }
+
+    #[test]
+    fn short_rows_are_padded_with_the_background() {
+        let image = Image::from_grid(&Grid(vec![vec![1], vec![1, 1]]), |_| WHITE);
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert_eq!(image.get((0, 1)), Some(BACKGROUND));
+        assert_eq!(image.get((0, 2)), None);
+        assert_eq!(image.get((2, 0)), None);
+    }
+
+    #[test]
+    fn heat_palette() {
+        assert_eq!(heat(0.0, 0.0, 4.0), [68, 1, 84]);
+        assert_eq!(heat(4.0, 0.0, 4.0), [253, 231, 37]);
+        assert_eq!(heat(9.0, 0.0, 4.0), [253, 231, 37]);
+        assert_eq!(heat(2.0, 0.0, 4.0), [33, 145, 140]);
+        assert_eq!(heat(1.0, 1.0, 1.0), [68, 1, 84]);
+        let map = Image::height_map(&Grid(vec![vec![3u8, 5, 7]]));
+        assert_eq!(map.get((0, 0)), Some(heat(0.0, 0.0, 1.0)));
+        assert_eq!(map.get((0, 2)), Some(heat(1.0, 0.0, 1.0)));
+    }
+
+    #[test]
+    fn overlay_blends_inside_the_image() {
+        let mut image = diagonal();
+        image.overlay([(0, 1), (5, 5)], [0, 0, 255], 0.5);
+        assert_eq!(image.get((0, 1)), Some([128, 128, 255]));
+        image.overlay([(1, 0)], [0, 0, 0], 2.0);
+        assert_eq!(image.get((1, 0)), Some([0, 0, 0]));
+    }
+
+    #[test]
+    fn scaled_cells_are_squares() {
+        let image = diagonal().scaled(2);
+        assert_eq!((image.width(), image.height()), (4, 4));
+        assert_eq!(image.get((1, 1)), Some(RED));
+        assert_eq!(image.get((1, 2)), Some(WHITE));
+        assert_eq!(image.get((3, 3)), Some(RED));
+        assert_eq!(diagonal().scaled(0), diagonal());
+    }
+
+    #[test]
+    fn ppm() {
+        let mut out = vec![];
+        diagonal().write_ppm(&mut out).unwrap();
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([RED, WHITE, WHITE, RED].as_flattened());
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn png_round_trip() {
+        let mut out = vec![];
+        diagonal().write_png(&mut out).unwrap();
+        let mut reader = png::Decoder::new(out.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (2, 2));
+        assert_eq!(pixels, [RED, WHITE, WHITE, RED].as_flattened());
+    }
+
+    #[test]
+    fn scene_highlights() {
+        let mut scene = Scene::new(Grid(vec![vec![Tile::new('.', ""); 2]]));
+        scene.push("").mark_glyph((0, 1), Highlight::Current, '#');
+        let image = Image::from_scene(&scene, 0);
+        assert_eq!(image.get((0, 0)), Some(glyph_colour('.')));
+        assert_eq!(image.get((0, 1)), Some([250, 210, 40]));
+    }
+
+    #[test]
+    fn sequences_are_numbered_by_format() {
+        let dir = temp_dir("frames");
+        let paths = save_sequence(&dir, ImageFormat::Ppm, [diagonal(), diagonal()]).unwrap();
+        assert_eq!(
+            paths,
+            [dir.join("frame-00000.ppm"), dir.join("frame-00001.ppm")]
+        );
+        assert!(paths.iter().all(|path| path.exists()));
+        assert_eq!(
+            ImageFormat::from_path(Path::new("a.png")).unwrap(),
+            ImageFormat::Png
+        );
+        assert!(ImageFormat::from_path(Path::new("a.jpg")).is_err());
+        assert!(diagonal().save(&dir.join("frame")).is_err());
+    }
+}
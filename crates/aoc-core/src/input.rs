@@ -0,0 +1,142 @@
+//! Puzzle inputs, read at runtime from `inputs/<year>/day-N.txt` at the root of
+//! the workspace.
+//!
+//! The directory can be moved with `AOC_INPUTS=<dir>`. Missing inputs are
+//! downloaded by `fetch`, with the `remote` feature.
+//!
+//! Checkouts from before years had directories of their own keep the 2024
+//! inputs at `inputs/day-N.txt`, which is still read when the new path is
+//! missing.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+#[cfg(feature = "remote")]
+use crate::remote::Client;
+
+/// The year whose inputs used to sit directly in [`dir`].
+const LEGACY_YEAR: u16 = 2024;
+
+/// The directory holding the inputs of every year.
+pub fn dir() -> PathBuf {
+    std::env::var_os("AOC_INPUTS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../inputs"))
+}
+
+pub fn year_dir(year: u16) -> PathBuf {
+    dir().join(year.to_string())
+}
+
+pub fn path(year: u16, day: u8) -> PathBuf {
+    path_in(&dir(), year, day)
+}
+
+fn path_in(dir: &Path, year: u16, day: u8) -> PathBuf {
+    let name = format!("day-{day}.txt");
+    let path = dir.join(year.to_string()).join(&name);
+    let legacy = dir.join(&name);
+    match year == LEGACY_YEAR && !path.exists() && legacy.exists() {
+        true => legacy,
+        false => path,
+    }
+}
+
+pub fn load(year: u16, day: u8) -> anyhow::Result<String> {
+    let path = path(year, day);
+    std::fs::read_to_string(&path).with_context(|| format!("Could not read {}", path.display()))
+}
+
+/// Downloads the input of `day` to [`path`], unless it is already there.
+/// Returns whether it was downloaded.
+#[cfg(feature = "remote")]
+pub fn fetch(client: &Client, year: u16, day: u8) -> anyhow::Result<bool> {
+    download(client, year, day, &path(year, day))
+}
+
+/// Downloads the input of `day` to `path`, unless it is already there.
+#[cfg(feature = "remote")]
+pub fn download(client: &Client, year: u16, day: u8, path: &Path) -> anyhow::Result<bool> {
+    if path.exists() {
+        return Ok(false);
+    }
+    let input = client.get(&client.day_url(year, day, "/input"))?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    // a partial file would be mistaken for a cached input
+    let partial = path.with_extension("txt.part");
+    std::fs::write(&partial, input)
+        .and_then(|_| std::fs::rename(&partial, path))
+        .with_context(|| format!("Could not write {}", path.display()))?;
+    Ok(true)
+}
+
+/// FNV-1a hash of the input, to tell which input a result was computed from.
+/// Stable across runs and platforms, unlike `std`'s hashers.
+pub fn hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "remote")]
+    use std::time::Duration;
+
+    use super::*;
+    #[cfg(feature = "remote")]
+    use crate::remote::stub::Stub;
+    use crate::temp_dir;
+
+    #[test]
+    fn falls_back_to_the_old_path() {
+        let dir = temp_dir("legacy");
+        let new = dir.join("2024").join("day-1.txt");
+        let legacy = dir.join("day-1.txt");
+        assert_eq!(path_in(&dir, 2024, 1), new);
+        std::fs::write(&legacy, "").unwrap();
+        assert_eq!(path_in(&dir, 2024, 1), legacy);
+        assert_eq!(path_in(&dir, 2023, 1), dir.join("2023").join("day-1.txt"));
+        std::fs::create_dir(dir.join("2024")).unwrap();
+        std::fs::write(&new, "").unwrap();
+        assert_eq!(path_in(&dir, 2024, 1), new);
+    }
+
+    #[test]
+    #[cfg(feature = "remote")]
+    fn download_is_cached() {
+        let stub = Stub::start(|_| (200, "3   4\n4   3\n".to_string()));
+        let dir = temp_dir("download");
+        let client = Client::new(&stub.url, "secret")
+            .with_throttle(Duration::ZERO)
+            .with_state_dir(&dir);
+        let path = dir.join("2024").join("day-1.txt");
+        assert!(download(&client, 2024, 1, &path).unwrap());
+        assert!(!download(&client, 2024, 1, &path).unwrap());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "3   4\n4   3\n");
+        assert_eq!(stub.seen().len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "remote")]
+    fn failed_download_leaves_no_input() {
+        let stub = Stub::start(|_| (500, "Internal error".to_string()));
+        let dir = temp_dir("failed-download");
+        let client = Client::new(&stub.url, "secret")
+            .with_throttle(Duration::ZERO)
+            .with_state_dir(&dir);
+        let path = dir.join("2024").join("day-1.txt");
+        assert!(download(&client, 2024, 1, &path).is_err());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(hash(""), "cbf29ce484222325");
+        assert_eq!(hash("a"), "af63dc4c8601ec8c");
+    }
+}
@@ -0,0 +1,178 @@
+//! `aoc inspect <DAY>`: facts about a puzzle input, to look at before picking
+//! an algorithm.
+//!
+//! Every input gets the generic statistics from [`raw_stats`], days add their
+//! own through [`Solution::inspect`](crate::Solution::inspect).
+
+use std::{fmt::Display, io::Write};
+
+use crate::solution::Solver;
+
+/// A list of named statistics, in the order they were added.
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    stats: Vec<(String, String)>,
+}
+
+impl Report {
+    pub fn add(&mut self, name: impl Into<String>, value: impl Display) -> &mut Self {
+        self.stats.push((name.into(), value.to_string()));
+        self
+    }
+
+    pub fn stats(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.stats
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    /// Writes one `name  value` line per statistic, with the values aligned.
+    pub fn write(&self, mut w: impl Write) -> anyhow::Result<()> {
+        let width = self
+            .stats
+            .iter()
+            .map(|(name, _)| name.chars().count())
+            .max()
+            .unwrap_or(0);
+        for (name, value) in &self.stats {
+            writeln!(w, "{name:<width$}  {value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Statistics that only need the text of the input: its size, the length of
+/// its lines, the range of the numbers in it and, when every line has the same
+/// length, its grid dimensions.
+pub fn raw_stats(input: &str, report: &mut Report) {
+    let lines: Vec<&str> = input.lines().collect();
+    report
+        .add("bytes", input.len())
+        .add("lines", lines.len())
+        .add("blank lines", lines.iter().filter(|l| l.is_empty()).count());
+
+    let lengths = lines.iter().map(|line| line.chars().count());
+    if let (Some(min), Some(max)) = (lengths.clone().min(), lengths.max()) {
+        report.add("line length", range(min, max));
+        if min == max && lines.len() > 1 {
+            report.add("grid", format!("{min}x{}", lines.len()));
+        }
+    }
+
+    let numbers: Vec<i64> = lines.iter().flat_map(|line| numbers(line)).collect();
+    if let (Some(min), Some(max)) = (numbers.iter().min(), numbers.iter().max()) {
+        report
+            .add("numbers", numbers.len())
+            .add("number range", range(min, max));
+    }
+
+    let mut chars: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
+    chars.sort_unstable();
+    chars.dedup();
+    if chars.len() <= 16 {
+        report.add("characters", format!("{:?}", String::from_iter(chars)));
+    } else {
+        report.add("distinct characters", chars.len());
+    }
+}
+
+/// `a..=b`, or just `a` when both ends are the same.
+pub fn range<T: Display + PartialEq>(min: T, max: T) -> String {
+    match min == max {
+        true => min.to_string(),
+        false => format!("{min}..={max}"),
+    }
+}
+
+/// The integers in `line`, a `-` directly before the digits makes them negative.
+fn numbers(line: &str) -> impl Iterator<Item = i64> + '_ {
+    let mut rest = line;
+    std::iter::from_fn(move || {
+        let start = rest.find(|c: char| c.is_ascii_digit())?;
+        let len = rest[start..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len() - start);
+        let negative = rest[..start].ends_with('-');
+        let digits = &rest[start..start + len];
+        rest = &rest[start + len..];
+        // numbers too big for an i64 show up as i64::MAX
+        let number = digits.parse::<i64>().unwrap_or(i64::MAX);
+        Some(if negative { -number } else { number })
+    })
+}
+
+/// The generic and the day specific statistics of `input`. The generic ones
+/// are still reported when `input` does not parse.
+pub fn inspect(solver: &dyn Solver, input: &str) -> (Report, anyhow::Result<()>) {
+    let mut report = Report::default();
+    raw_stats(input, &mut report);
+    let result = solver
+        .parse(input)
+        .and_then(|parsed| solver.inspect(&parsed, &mut report));
+    (report, result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(input: &str) -> Vec<(String, String)> {
+        let mut report = Report::default();
+        raw_stats(input, &mut report);
+        report
+            .stats()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn stat(stats: &[(String, String)], name: &str) -> Option<String> {
+        stats
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.clone())
+    }
+
+    #[test]
+    fn empty_input() {
+        let stats = stats("");
+        assert_eq!(stat(&stats, "bytes").as_deref(), Some("0"));
+        assert_eq!(stat(&stats, "lines").as_deref(), Some("0"));
+        assert_eq!(stat(&stats, "line length"), None);
+        assert_eq!(stat(&stats, "numbers"), None);
+        assert_eq!(stat(&stats, "characters").as_deref(), Some("\"\""));
+    }
+
+    #[test]
+    fn single_line() {
+        let stats = stats("abc 12\n");
+        assert_eq!(stat(&stats, "lines").as_deref(), Some("1"));
+        assert_eq!(stat(&stats, "line length").as_deref(), Some("6"));
+        // a single line is not a grid
+        assert_eq!(stat(&stats, "grid"), None);
+        assert_eq!(stat(&stats, "numbers").as_deref(), Some("1"));
+        assert_eq!(stat(&stats, "number range").as_deref(), Some("12"));
+    }
+
+    #[test]
+    fn grid_dimensions() {
+        let stats = stats("ab\ncd\nef\n");
+        assert_eq!(stat(&stats, "grid").as_deref(), Some("2x3"));
+    }
+
+    #[test]
+    fn signed_and_unsigned_numbers() {
+        let line = "p=0,4 v=-3,17 x-2 99999999999999999999";
+        assert_eq!(
+            numbers(line).collect::<Vec<_>>(),
+            [0, 4, -3, 17, -2, i64::MAX]
+        );
+
+        let stats = stats("1 -20\n300 4\n");
+        assert_eq!(stat(&stats, "numbers").as_deref(), Some("4"));
+        assert_eq!(stat(&stats, "number range").as_deref(), Some("-20..=300"));
+    }
+}
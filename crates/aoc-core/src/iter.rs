@@ -0,0 +1,115 @@
+//! Iterator adapters missing from stable Rust.
+
+/// Overlapping windows of `N` consecutive items, see
+/// [`ArrayWindowsExt::array_windows`].
+#[derive(Debug, Clone)]
+pub struct ArrayWindows<I: Iterator, const N: usize> {
+    iter: I,
+    window: Option<[I::Item; N]>,
+}
+
+impl<I, const N: usize> Iterator for ArrayWindows<I, N>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item; N];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.window {
+            Some(window) => {
+                let next = self.iter.next()?;
+                window.rotate_left(1);
+                window[N - 1] = next;
+            }
+            None => {
+                let first: Vec<I::Item> = self.iter.by_ref().take(N).collect();
+                self.window = Some(first.try_into().ok()?);
+            }
+        }
+        self.window.clone()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (low, high) = self.iter.size_hint();
+        match self.window {
+            Some(_) => (low, high),
+            None => (
+                low.saturating_sub(N - 1),
+                high.map(|high| high.saturating_sub(N - 1)),
+            ),
+        }
+    }
+}
+
+impl<I, const N: usize> ExactSizeIterator for ArrayWindows<I, N>
+where
+    I: ExactSizeIterator,
+    I::Item: Clone,
+{
+}
+
+pub trait ArrayWindowsExt: Iterator + Sized {
+    /// Every run of `N` consecutive items as an array, like
+    /// [`slice::windows`] but for any iterator: `[1, 2, 3]` gives `[1, 2]` and
+    /// `[2, 3]`. `N` must not be 0.
+    fn array_windows<const N: usize>(self) -> ArrayWindows<Self, N>
+    where
+        Self::Item: Clone,
+    {
+        assert!(N > 0, "windows must hold at least one item");
+        ArrayWindows {
+            iter: self,
+            window: None,
+        }
+    }
+}
+
+impl<I: Iterator> ArrayWindowsExt for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows() {
+        let windows: Vec<[u8; 2]> = [1, 2, 3].into_iter().array_windows().collect();
+        assert_eq!(windows, [[1, 2], [2, 3]]);
+    }
+
+    #[test]
+    fn too_short_for_a_window() {
+        assert_eq!([1, 2].into_iter().array_windows::<3>().next(), None);
+        assert_eq!(std::iter::empty::<u8>().array_windows::<1>().next(), None);
+    }
+
+    #[test]
+    fn exactly_one_window() {
+        let mut windows = [1, 2, 3].into_iter().array_windows::<3>();
+        assert_eq!(windows.next(), Some([1, 2, 3]));
+        assert_eq!(windows.next(), None);
+    }
+
+    #[test]
+    fn len_matches_the_windows() {
+        for len in 0..6usize {
+            let mut windows = (0..len).array_windows::<3>();
+            let mut left = windows.clone().count();
+            assert_eq!(left, len.saturating_sub(2), "{len} items");
+            loop {
+                assert_eq!(windows.len(), left, "{len} items");
+                assert_eq!(windows.size_hint(), (left, Some(left)));
+                if windows.next().is_none() {
+                    break;
+                }
+                left -= 1;
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic = "at least one item"]
+    fn empty_windows_panic() {
+        let _ = [1].into_iter().array_windows::<0>();
+    }
+}
@@ -0,0 +1,387 @@
+//! `aoc leaderboard <FILE>`: reports on an exported private leaderboard.
+//!
+//! The JSON is the one served at `/2024/leaderboard/private/view/<id>.json`.
+//! Times are measured from the moment each puzzle unlocked, midnight EST.
+
+use std::{collections::BTreeMap, io::Write, path::Path, time::Duration};
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
+
+use crate::{output::Format, solution::Part};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Members without a name are shown as `(anonymous user #id)`, like the site does.
+    pub name: Option<String>,
+    #[serde(default)]
+    pub local_score: u64,
+    #[serde(default)]
+    pub stars: u32,
+    /// Day, then part, to the time the star was earned.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Star {
+    /// Unix timestamp, in seconds.
+    pub get_star_ts: i64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+}
+
+/// How long one member took for the parts of one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySolve {
+    pub member_id: u64,
+    /// The [display name](Member::display_name), which two members can share.
+    pub member: String,
+    pub day: u8,
+    pub part_1: Option<Duration>,
+    pub part_2: Option<Duration>,
+}
+
+impl DaySolve {
+    /// Time between the two stars.
+    pub fn delta(&self) -> Option<Duration> {
+        self.part_2?.checked_sub(self.part_1?)
+    }
+}
+
+impl Leaderboard {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read {}", path.display()))?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Invalid leaderboard {}", path.display()))
+    }
+
+    pub fn year(&self) -> anyhow::Result<i64> {
+        self.event
+            .parse()
+            .with_context(|| format!("Invalid event `{}`", self.event))
+    }
+
+    /// Every day every member earned at least one star on, by day then member.
+    pub fn solves(&self) -> anyhow::Result<Vec<DaySolve>> {
+        let year = self.year()?;
+        let mut solves = vec![];
+        for member in self.members.values() {
+            for (&day, stars) in &member.completion_day_level {
+                let unlock = unlock_time(year, day);
+                let time = |part: Part| {
+                    let star = stars.get(&part.number())?;
+                    let seconds = u64::try_from(star.get_star_ts - unlock).ok()?;
+                    Some(Duration::from_secs(seconds))
+                };
+                solves.push(DaySolve {
+                    member_id: member.id,
+                    member: member.display_name(),
+                    day,
+                    part_1: time(Part::One),
+                    part_2: time(Part::Two),
+                });
+            }
+        }
+        solves
+            .sort_by(|a, b| (a.day, &a.member, a.member_id).cmp(&(b.day, &b.member, b.member_id)));
+        Ok(solves)
+    }
+}
+
+/// Unix timestamp of the unlock of `day`, midnight EST (UTC-5) on December `day`.
+pub fn unlock_time(year: i64, day: u8) -> i64 {
+    days_from_civil(year, 12, day as i64) * 24 * 60 * 60 + 5 * 60 * 60
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date, from Howard Hinnant's
+/// `days_from_civil`.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// `h:mm:ss`, hours are not wrapped into days.
+pub fn format_time(time: Option<Duration>) -> String {
+    match time {
+        Some(time) => {
+            let seconds = time.as_secs();
+            format!(
+                "{}:{:02}:{:02}",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            )
+        }
+        None => "-".to_string(),
+    }
+}
+
+/// Writes the star timeline of every member, then the ranking of every day.
+/// Members of a day are ranked by their part 2 time, then their part 1 time.
+pub fn write_report(board: &Leaderboard, format: Format, mut w: impl Write) -> anyhow::Result<()> {
+    let markdown = match format {
+        Format::Text => false,
+        Format::Markdown => true,
+        _ => bail!("Leaderboards are written as text or markdown"),
+    };
+    let solves = board.solves()?;
+    let time = |solve: &DaySolve| {
+        [
+            format_time(solve.part_1),
+            format_time(solve.part_2),
+            format_time(solve.delta()),
+        ]
+    };
+
+    let mut members: Vec<&Member> = board.members.values().collect();
+    members.sort_by_key(|member| std::cmp::Reverse(member.local_score));
+    for member in members {
+        heading(
+            &mut w,
+            markdown,
+            &format!(
+                "{}: {} stars, {} points",
+                member.display_name(),
+                member.stars,
+                member.local_score
+            ),
+        )?;
+        let rows: Vec<Vec<String>> = solves
+            .iter()
+            .filter(|solve| solve.member_id == member.id)
+            .map(|solve| [vec![solve.day.to_string()], time(solve).to_vec()].concat())
+            .collect();
+        table(
+            &mut w,
+            markdown,
+            &["Day", "Part 1", "Part 2", "Delta"],
+            None,
+            &rows,
+        )?;
+    }
+
+    let mut by_day: BTreeMap<u8, Vec<&DaySolve>> = BTreeMap::new();
+    for solve in &solves {
+        by_day.entry(solve.day).or_default().push(solve);
+    }
+    for (day, mut solves) in by_day {
+        // unfinished parts go last
+        solves.sort_by_key(|solve| {
+            (
+                solve.part_2.unwrap_or(Duration::MAX),
+                solve.part_1.unwrap_or(Duration::MAX),
+            )
+        });
+        heading(&mut w, markdown, &format!("Day {day}"))?;
+        let rows: Vec<Vec<String>> = solves
+            .iter()
+            .enumerate()
+            .map(|(i, solve)| {
+                [
+                    vec![(i + 1).to_string(), solve.member.clone()],
+                    time(solve).to_vec(),
+                ]
+                .concat()
+            })
+            .collect();
+        table(
+            &mut w,
+            markdown,
+            &["Rank", "Member", "Part 1", "Part 2", "Delta"],
+            Some(1),
+            &rows,
+        )?;
+    }
+    Ok(())
+}
+
+fn heading(w: &mut impl Write, markdown: bool, title: &str) -> anyhow::Result<()> {
+    match markdown {
+        true => writeln!(w, "## {title}\n")?,
+        false => writeln!(w, "{title}\n{}", "=".repeat(title.chars().count()))?,
+    }
+    Ok(())
+}
+
+/// Columns are right aligned, except for the text column `left`.
+fn table(
+    w: &mut impl Write,
+    markdown: bool,
+    header: &[&str],
+    left: Option<usize>,
+    rows: &[Vec<String>],
+) -> anyhow::Result<()> {
+    if markdown {
+        writeln!(w, "| {} |", header.join(" | "))?;
+        let align: Vec<&str> = (0..header.len())
+            .map(|i| if Some(i) == left { "---" } else { "---:" })
+            .collect();
+        writeln!(w, "| {} |", align.join(" | "))?;
+        for row in rows {
+            let cells: Vec<String> = row.iter().map(|cell| cell.replace('|', "\\|")).collect();
+            writeln!(w, "| {} |", cells.join(" | "))?;
+        }
+        writeln!(w)?;
+        return Ok(());
+    }
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain(Some(header[i].chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, &width))| match Some(i) == left {
+                true => format!("{cell:<width$}"),
+                false => format!("{cell:>width$}"),
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+    writeln!(w, "{}", line(header.to_vec()).trim_end())?;
+    for row in rows {
+        writeln!(
+            w,
+            "{}",
+            line(row.iter().map(String::as_str).collect()).trim_end()
+        )?;
+    }
+    writeln!(w)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_1: i64 = 1733029200;
+
+    fn board() -> Leaderboard {
+        let json = format!(
+            r#"{{
+                "event": "2024",
+                "members": {{
+                    "1": {{
+                        "id": 1, "name": "Ada", "local_score": 10, "stars": 3,
+                        "completion_day_level": {{
+                            "1": {{"1": {{"get_star_ts": {}}}, "2": {{"get_star_ts": {}}}}},
+                            "2": {{"1": {{"get_star_ts": {}}}}}
+                        }}
+                    }},
+                    "2": {{
+                        "id": 2, "name": "Ada", "local_score": 4, "stars": 1,
+                        "completion_day_level": {{
+                            "1": {{"1": {{"get_star_ts": {}}}}}
+                        }}
+                    }},
+                    "3": {{"id": 3, "name": null}}
+                }}
+            }}"#,
+            DAY_1 + 65,
+            DAY_1 + 3725,
+            DAY_1 + 86400 + 7,
+            DAY_1 + 30,
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(unlock_time(2024, 1), DAY_1);
+        assert_eq!(unlock_time(2024, 25), DAY_1 + 24 * 86400);
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(format_time(Some(Duration::from_secs(3725))), "1:02:05");
+        assert_eq!(
+            format_time(Some(Duration::from_secs(30 * 3600))),
+            "30:00:00"
+        );
+        assert_eq!(format_time(None), "-");
+    }
+
+    #[test]
+    fn solves() {
+        let solves = board().solves().unwrap();
+        let summary: Vec<_> = solves
+            .iter()
+            .map(|solve| (solve.day, solve.member_id, solve.part_1, solve.delta()))
+            .collect();
+        let secs = |secs| Some(Duration::from_secs(secs));
+        assert_eq!(
+            summary,
+            [
+                (1, 1, secs(65), secs(3660)),
+                (1, 2, secs(30), None),
+                (2, 1, secs(7), None)
+            ]
+        );
+        assert_eq!(board().members["3"].display_name(), "(anonymous user #3)");
+    }
+
+    #[test]
+    fn members_sharing_a_name_keep_their_own_rows() {
+        let mut report = vec![];
+        write_report(&board(), Format::Text, &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        let expected = "\
+Ada: 3 stars, 10 points
+=======================
+Day   Part 1   Part 2    Delta
+  1  0:01:05  1:02:05  1:01:00
+  2  0:00:07        -        -
+
+Ada: 1 stars, 4 points
+======================
+Day   Part 1  Part 2  Delta
+  1  0:00:30       -      -
+
+";
+        assert!(report.starts_with(expected), "{report}");
+    }
+
+    #[test]
+    fn markdown_ranking() {
+        let mut report = vec![];
+        write_report(&board(), Format::Markdown, &mut report).unwrap();
+        let report = String::from_utf8(report).unwrap();
+        let expected = "\
+## Day 1
+
+| Rank | Member | Part 1 | Part 2 | Delta |
+| ---: | --- | ---: | ---: | ---: |
+| 1 | Ada | 0:01:05 | 1:02:05 | 1:01:00 |
+| 2 | Ada | 0:00:30 | - | - |
+";
+        assert!(report.contains(expected), "{report}");
+        assert!(write_report(&board(), Format::JsonLines, vec![]).is_err());
+    }
+}
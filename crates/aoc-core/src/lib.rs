@@ -0,0 +1,252 @@
+#![cfg_attr(feature = "simd", feature(portable_simd))]
+//! The parts of the solutions that do not depend on the year: the [`Solution`]
+//! trait and the [registry](registry::Registry) of solvers, the runner and the
+//! CLI subcommands built on it, plus [`Grid`], [`Point`], tracing and progress
+//! reporting.
+//!
+//! Every year is a crate of its own that implements [`Solution`] for its days
+//! and depends on this one for everything else.
+//!
+//! The modules that pull in heavy dependencies are behind default features:
+//! `explore`, `image`, `remote` (with [`input::fetch`] and [`submit::submit`])
+//! and `serve`.
+
+use std::{
+    ops::{Index, IndexMut},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use tracing::Subscriber;
+use tracing_chrome::{ChromeLayer, ChromeLayerBuilder, FlushGuard};
+use tracing_indicatif::IndicatifLayer;
+use tracing_subscriber::{
+    layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt, EnvFilter,
+};
+
+pub mod alloc;
+pub mod answers;
+pub mod asciicast;
+pub mod cancel;
+#[cfg(feature = "explore")]
+pub mod explore;
+pub mod generate;
+#[cfg(feature = "image")]
+pub mod image;
+pub mod input;
+pub mod inspect;
+pub mod iter;
+pub mod leaderboard;
+pub mod memo;
+pub mod output;
+pub mod point;
+pub mod progress;
+pub mod registry;
+#[cfg(feature = "remote")]
+pub mod remote;
+pub mod runner;
+pub mod scene;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod snapshot;
+pub mod solution;
+pub mod submit;
+pub mod summary;
+pub mod validate;
+pub mod watch;
+
+pub use point::Point;
+pub use registry::Registry;
+pub use solution::{Answer, Part, Solution, Solver};
+
+/// Options for [`init_tracing_with`].
+#[derive(Debug, Clone)]
+pub struct TracingOptions {
+    /// Also write span enter/exit events to this file in the Chrome trace-event
+    /// format, for viewing in `chrome://tracing` or ui.perfetto.dev.
+    pub chrome_trace: Option<PathBuf>,
+    /// Draw the bars of the [`progress`] adapters.
+    pub progress: bool,
+}
+
+impl Default for TracingOptions {
+    fn default() -> Self {
+        Self {
+            chrome_trace: None,
+            progress: true,
+        }
+    }
+}
+
+impl TracingOptions {
+    /// Reads the options from the environment:
+    /// - `AOC_CHROME_TRACE=<path>` enables the Chrome trace output.
+    /// - `AOC_PROGRESS=0` disables progress bars.
+    pub fn from_env() -> Self {
+        Self {
+            chrome_trace: std::env::var_os("AOC_CHROME_TRACE").map(PathBuf::from),
+            progress: std::env::var("AOC_PROGRESS").map_or(true, |value| value != "0"),
+        }
+    }
+}
+
+/// Keeps the tracing outputs alive. The Chrome trace file is only complete once
+/// this is dropped, so hold on to it until the end of `main`.
+#[must_use = "dropping the guard finishes the chrome trace"]
+pub struct TracingGuard {
+    _chrome: Option<FlushGuard>,
+}
+
+pub fn init_tracing() -> anyhow::Result<TracingGuard> {
+    init_tracing_with(TracingOptions::from_env())
+}
+
+pub fn init_tracing_with(options: TracingOptions) -> anyhow::Result<TracingGuard> {
+    let env_filter = EnvFilter::builder()
+        .with_default_directive("info".parse()?)
+        .from_env_lossy();
+    eprintln!("using env filter {:?}", env_filter);
+    let indicatif_layer = IndicatifLayer::new();
+    let (chrome_layer, chrome_guard) = match &options.chrome_trace {
+        Some(path) => {
+            eprintln!("writing chrome trace to {}", path.display());
+            let (layer, guard) = chrome_layer(path)?;
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_writer(indicatif_layer.get_stderr_writer()))
+        .with(indicatif_layer)
+        .with(env_filter)
+        .with(chrome_layer)
+        .init();
+    progress::set_enabled(options.progress);
+    Ok(TracingGuard {
+        _chrome: chrome_guard,
+    })
+}
+
+/// A layer writing a Chrome trace to `path`, creating its directory. Unlike
+/// [`ChromeLayerBuilder::file`] this does not panic when the file cannot be
+/// created.
+fn chrome_layer<S>(path: &Path) -> anyhow::Result<(ChromeLayer<S>, FlushGuard)>
+where
+    S: Subscriber + for<'span> LookupSpan<'span> + Send + Sync,
+{
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Could not create {}", dir.display()))?;
+    }
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Could not create the chrome trace {}", path.display()))?;
+    Ok(ChromeLayerBuilder::new()
+        .writer(file)
+        .include_args(true)
+        .build())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T>(pub Vec<Vec<T>>);
+
+impl<T> Grid<T> {
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.0.iter().flat_map(|row| row.iter())
+    }
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.0.iter_mut().flat_map(|row| row.iter_mut())
+    }
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.0
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, t)| ((y, x), t)))
+    }
+    pub fn indexed_iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        self.0
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter_mut().enumerate().map(move |(x, t)| ((y, x), t)))
+    }
+    pub fn get(&self, index: (usize, usize)) -> Option<&T> {
+        self.0.get(index.0).and_then(|row| row.get(index.1))
+    }
+    pub fn get_mut(&mut self, index: (usize, usize)) -> Option<&mut T> {
+        self.0.get_mut(index.0).and_then(|row| row.get_mut(index.1))
+    }
+    pub fn bounds_check(&self, index: impl TryInto<(usize, usize)>) -> bool {
+        let index = index.try_into();
+        index.ok().and_then(|index| self.get(index)).is_some()
+    }
+    pub fn width(&self) -> usize {
+        self.0.len()
+    }
+    pub fn height(&self) -> usize {
+        self.0.first().map(|row| row.len()).unwrap_or(0)
+    }
+}
+
+impl<T, I> Index<I> for Grid<T>
+where
+    I: Into<(usize, usize)>,
+{
+    type Output = T;
+
+    fn index(&self, index: I) -> &Self::Output {
+        let (y, x): (usize, usize) = index.into();
+        &self.0[y][x]
+    }
+}
+
+impl<T, I> IndexMut<I> for Grid<T>
+where
+    I: Into<(usize, usize)>,
+{
+    fn index_mut(&mut self, index: I) -> &mut T {
+        let (y, x): (usize, usize) = index.into();
+        &mut self.0[y][x]
+    }
+}
+
+/// An empty directory for one test, under the system temp directory.
+#[cfg(test)]
+pub(crate) fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-core-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chrome_trace_is_json() {
+        let path = temp_dir("chrome-trace").join("traces/run.json");
+        let (layer, guard) = chrome_layer(&path).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            let _span = tracing::info_span!("solve", day = 1).entered();
+            tracing::info!("solved");
+        });
+        drop(guard);
+
+        let trace: Vec<serde_json::Value> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(trace.iter().any(|event| event["name"] == "solve"));
+    }
+
+    #[test]
+    fn chrome_trace_path_errors() {
+        let dir = temp_dir("chrome-trace-error");
+        let file = dir.join("file");
+        std::fs::write(&file, "").unwrap();
+        // a directory inside a regular file cannot be created
+        let error = chrome_layer::<tracing_subscriber::Registry>(&file.join("run.json"))
+            .err()
+            .unwrap();
+        assert!(error.to_string().starts_with("Could not create"));
+    }
+}
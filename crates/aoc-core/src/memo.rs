@@ -0,0 +1,293 @@
+//! Memoisation caches that keep track of their hit rate.
+//!
+//! - [`Memo`] is a plain single threaded cache, [`SharedMemo`] lets recursive
+//!   solvers either own one or borrow the caller's.
+//! - [`ConcurrentMemo`] can be shared between rayon workers.
+//!
+//! Both record into [`CacheStats`], which can be merged across workers with
+//! [`CacheStats::compose`] and logged with [`CacheStats::report`].
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    iter::Sum,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        RwLock,
+    },
+};
+
+#[derive(Debug, Default)]
+pub struct CacheStats {
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+impl CacheStats {
+    pub fn hit(&self) -> &Self {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        self
+    }
+    pub fn miss(&self) -> &Self {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        self
+    }
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+    pub fn accesses(&self) -> usize {
+        self.hits() + self.misses()
+    }
+    /// Fraction of accesses that were hits, `NaN` if the cache was never used.
+    pub fn accuracy(&self) -> f64 {
+        self.hits() as f64 / self.accesses() as f64
+    }
+    pub fn compose(&self, other: &Self) -> Self {
+        Self {
+            hits: (self.hits() + other.hits()).into(),
+            misses: (self.misses() + other.misses()).into(),
+        }
+    }
+    /// Logs the stats as an info event of the `aoc_core::memo` target.
+    pub fn report(&self, cache: &str) {
+        tracing::info!(
+            cache,
+            hits = self.hits(),
+            misses = self.misses(),
+            accuracy = format_args!("{:.2}", self.accuracy()),
+            "cache stats"
+        );
+    }
+}
+
+impl Clone for CacheStats {
+    fn clone(&self) -> Self {
+        Self {
+            hits: self.hits().into(),
+            misses: self.misses().into(),
+        }
+    }
+}
+
+impl Sum for CacheStats {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |acc, el| acc.compose(&el))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    map: HashMap<K, V>,
+    stats: CacheStats,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            map: HashMap::new(),
+            stats: CacheStats::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Looks up `key`, counting a hit or a miss.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let value = self.map.get(key);
+        match value {
+            Some(_) => self.stats.hit(),
+            None => self.stats.miss(),
+        };
+        value
+    }
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.map.insert(key, value)
+    }
+    /// Returns the cached value for `key`, computing and caching it on a miss.
+    pub fn get_or_insert_with(&mut self, key: K, f: impl FnOnce() -> V) -> &V {
+        let stats = &self.stats;
+        self.map
+            .entry(key)
+            .and_modify(|_| {
+                stats.hit();
+            })
+            .or_insert_with(|| {
+                stats.miss();
+                f()
+            })
+    }
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+    pub fn stats(&self) -> &CacheStats {
+        &self.stats
+    }
+    pub fn into_stats(self) -> CacheStats {
+        self.stats
+    }
+}
+
+/// A [`Memo`] that is either owned or mutably borrowed, so a recursive solver
+/// can create the cache on the first call and lend it to the deeper ones.
+#[derive(Debug)]
+pub enum SharedMemo<'a, K, V> {
+    Owned(Memo<K, V>),
+    MutBorrow(&'a mut Memo<K, V>),
+}
+
+impl<K, V> AsRef<Memo<K, V>> for SharedMemo<'_, K, V> {
+    fn as_ref(&self) -> &Memo<K, V> {
+        match self {
+            Self::Owned(memo) => memo,
+            Self::MutBorrow(memo) => memo,
+        }
+    }
+}
+
+impl<K, V> AsMut<Memo<K, V>> for SharedMemo<'_, K, V> {
+    fn as_mut(&mut self) -> &mut Memo<K, V> {
+        match self {
+            Self::Owned(memo) => memo,
+            Self::MutBorrow(memo) => memo,
+        }
+    }
+}
+
+/// A cache that can be shared between threads. Values are cloned out of it.
+#[derive(Debug)]
+pub struct ConcurrentMemo<K, V> {
+    map: RwLock<HashMap<K, V>>,
+    stats: CacheStats,
+}
+
+impl<K, V> Default for ConcurrentMemo<K, V> {
+    fn default() -> Self {
+        Self {
+            map: RwLock::new(HashMap::new()),
+            stats: CacheStats::default(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> ConcurrentMemo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Looks up `key`, counting a hit or a miss.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let value = self
+            .map
+            .read()
+            .expect("memo lock poisoned")
+            .get(key)
+            .cloned();
+        match value {
+            Some(_) => self.stats.hit(),
+            None => self.stats.miss(),
+        };
+        value
+    }
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.map
+            .write()
+            .expect("memo lock poisoned")
+            .insert(key, value)
+    }
+    /// Returns the cached value for `key`, computing and caching it on a miss.
+    ///
+    /// `f` runs without holding the lock, so two threads missing the same key
+    /// at the same time may both compute it.
+    pub fn get_or_insert_with(&self, key: K, f: impl FnOnce() -> V) -> V {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = f();
+        self.insert(key, value.clone());
+        value
+    }
+    pub fn len(&self) -> usize {
+        self.map.read().expect("memo lock poisoned").len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    pub fn stats(&self) -> &CacheStats {
+        &self.stats
+    }
+    pub fn into_stats(self) -> CacheStats {
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rayon::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn memo_counts_hits_and_misses() {
+        let mut memo = Memo::new();
+        assert_eq!(memo.get(&1), None);
+        assert_eq!(*memo.get_or_insert_with(1, || "one"), "one");
+        assert_eq!(*memo.get_or_insert_with(1, || unreachable!()), "one");
+        assert_eq!(memo.get(&1), Some(&"one"));
+        assert_eq!(memo.len(), 1);
+        let stats = memo.into_stats();
+        assert_eq!((stats.hits(), stats.misses()), (2, 2));
+        assert_eq!(stats.accuracy(), 0.5);
+    }
+
+    #[test]
+    fn shared_memo_lends_the_cache() {
+        fn fib(n: u64, mut memo: SharedMemo<u64, u64>) -> u64 {
+            if n < 2 {
+                return n;
+            }
+            if let Some(&value) = memo.as_ref().get(&n) {
+                return value;
+            }
+            let value = fib(n - 1, SharedMemo::MutBorrow(memo.as_mut()))
+                + fib(n - 2, SharedMemo::MutBorrow(memo.as_mut()));
+            memo.as_mut().insert(n, value);
+            value
+        }
+        let mut memo = Memo::new();
+        assert_eq!(fib(50, SharedMemo::MutBorrow(&mut memo)), 12586269025);
+        assert_eq!(memo.len(), 49);
+    }
+
+    #[test]
+    fn concurrent_memo_across_workers() {
+        let memo = ConcurrentMemo::new();
+        let total: u64 = (0..1000u64)
+            .into_par_iter()
+            .map(|i| memo.get_or_insert_with(i % 10, || i % 10 * 2))
+            .sum();
+        assert_eq!(total, 9000);
+        assert_eq!(memo.len(), 10);
+        assert_eq!(memo.stats().accesses(), 1000);
+        assert!(memo.stats().misses() >= 10);
+    }
+
+    #[test]
+    fn stats_compose() {
+        let a = CacheStats::default();
+        a.hit().hit().miss();
+        let b = CacheStats::default();
+        b.miss();
+        let total: CacheStats = [a.clone(), b].into_iter().sum();
+        assert_eq!((total.hits(), total.misses()), (2, 2));
+        assert_eq!(a.compose(&CacheStats::default()).accesses(), 3);
+        assert!(CacheStats::default().accuracy().is_nan());
+    }
+}
@@ -0,0 +1,277 @@
+//! Machine readable run results: JSON lines, CSV and Markdown tables.
+//!
+//! Every part of every day becomes one [`Record`]. A day whose input could not
+//! be read or parsed still produces a failed record for each part.
+
+use std::{io::Write, str::FromStr};
+
+use anyhow::bail;
+use serde::Serialize;
+
+use crate::{
+    runner::{DayReport, PartReport, Status},
+    solution::Part,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Not machine readable, the runner prints a [summary](crate::summary) instead.
+    #[default]
+    Text,
+    JsonLines,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" | "jsonl" => Ok(Format::JsonLines),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => bail!("Unknown format `{s}`, expected text, json, csv or markdown"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// `solved`, `failed` or `timed_out`.
+    pub status: &'static str,
+    pub error: Option<String>,
+    pub duration_ms: f64,
+    pub input_hash: Option<String>,
+}
+
+impl Record {
+    pub fn from_part(part: &PartReport, input_hash: Option<String>) -> Self {
+        let (answer, status, error) = match &part.status {
+            Status::Solved(answer) => (Some(answer.to_string()), "solved", None),
+            Status::Failed(e) => (None, "failed", Some(e.clone())),
+            Status::TimedOut => (None, "timed_out", None),
+        };
+        Record {
+            year: part.year,
+            day: part.day,
+            part: part.part.number(),
+            answer,
+            status,
+            error,
+            duration_ms: part.elapsed.as_secs_f64() * 1000.0,
+            input_hash,
+        }
+    }
+}
+
+pub fn records(reports: &[DayReport]) -> Vec<Record> {
+    reports.iter().flat_map(day_records).collect()
+}
+
+fn day_records(report: &DayReport) -> Vec<Record> {
+    let input_hash = report.input_hash.clone();
+    if let Err(e) = &report.parse {
+        return Part::ALL
+            .into_iter()
+            .map(|part| Record {
+                year: report.year,
+                day: report.day,
+                part: part.number(),
+                answer: None,
+                status: "failed",
+                error: Some(e.clone()),
+                duration_ms: 0.0,
+                input_hash: input_hash.clone(),
+            })
+            .collect();
+    }
+    report
+        .parts
+        .iter()
+        .map(|part| Record::from_part(part, input_hash.clone()))
+        .collect()
+}
+
+pub fn write(format: Format, records: &[Record], mut w: impl Write) -> anyhow::Result<()> {
+    match format {
+        Format::Text => {}
+        Format::JsonLines => {
+            for record in records {
+                serde_json::to_writer(&mut w, record)?;
+                writeln!(w)?;
+            }
+        }
+        Format::Csv => {
+            writeln!(
+                w,
+                "year,day,part,answer,status,error,duration_ms,input_hash"
+            )?;
+            for record in records {
+                writeln!(
+                    w,
+                    "{},{},{},{},{},{},{:.3},{}",
+                    record.year,
+                    record.day,
+                    record.part,
+                    csv_field(record.answer.as_deref().unwrap_or("")),
+                    record.status,
+                    csv_field(record.error.as_deref().unwrap_or("")),
+                    record.duration_ms,
+                    record.input_hash.as_deref().unwrap_or(""),
+                )?;
+            }
+        }
+        Format::Markdown => {
+            writeln!(
+                w,
+                "| Year | Day | Part | Answer | Status | Time (ms) | Input |"
+            )?;
+            writeln!(w, "| ---: | ---: | ---: | ---: | --- | ---: | --- |")?;
+            for record in records {
+                let status = match &record.error {
+                    Some(e) => format!("{}: {}", record.status, e),
+                    None => record.status.to_string(),
+                };
+                writeln!(
+                    w,
+                    "| {} | {} | {} | {} | {} | {:.3} | {} |",
+                    record.year,
+                    record.day,
+                    record.part,
+                    markdown_cell(record.answer.as_deref().unwrap_or("")),
+                    markdown_cell(&status),
+                    record.duration_ms,
+                    record.input_hash.as_deref().unwrap_or(""),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn markdown_cell(cell: &str) -> String {
+    cell.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::solution::Answer;
+
+    fn part(day: u8, part: Part, status: Status) -> PartReport {
+        PartReport {
+            year: 2024,
+            day,
+            part,
+            status,
+            elapsed: Duration::from_micros(1500),
+            alloc: None,
+        }
+    }
+
+    /// Day 1 solves part 1 and fails part 2, day 2 does not parse.
+    fn reports() -> Vec<DayReport> {
+        vec![
+            DayReport {
+                year: 2024,
+                day: 1,
+                input_hash: Some("cbf29ce484222325".to_string()),
+                parse: Ok(Duration::ZERO),
+                parts: vec![
+                    part(1, Part::One, Status::Solved(Answer("11".to_string()))),
+                    part(1, Part::Two, Status::Failed("a \"b\", c|d\ne".to_string())),
+                ],
+            },
+            DayReport {
+                year: 2024,
+                day: 2,
+                input_hash: None,
+                parse: Err("No input".to_string()),
+                parts: vec![],
+            },
+        ]
+    }
+
+    fn written(format: Format) -> String {
+        let mut out = vec![];
+        write(format, &records(&reports()), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::JsonLines);
+        assert_eq!("md".parse::<Format>().unwrap(), Format::Markdown);
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn unparsed_days_fail_every_part() {
+        let records = records(&reports());
+        let statuses: Vec<_> = records.iter().map(|r| (r.day, r.part, r.status)).collect();
+        assert_eq!(
+            statuses,
+            [
+                (1, 1, "solved"),
+                (1, 2, "failed"),
+                (2, 1, "failed"),
+                (2, 2, "failed")
+            ]
+        );
+        assert_eq!(records[3].error.as_deref(), Some("No input"));
+    }
+
+    #[test]
+    fn json_lines() {
+        let json = written(Format::JsonLines);
+        let first = json.lines().next().unwrap();
+        assert_eq!(
+            first,
+            r#"{"year":2024,"day":1,"part":1,"answer":"11","status":"solved","error":null,"duration_ms":1.5,"input_hash":"cbf29ce484222325"}"#
+        );
+        assert_eq!(json.lines().count(), 4);
+    }
+
+    #[test]
+    fn csv_quotes_fields() {
+        let expected = "\
+year,day,part,answer,status,error,duration_ms,input_hash
+2024,1,1,11,solved,,1.500,cbf29ce484222325
+2024,1,2,,failed,\"a \"\"b\"\", c|d
+e\",1.500,cbf29ce484222325
+2024,2,1,,failed,No input,0.000,
+2024,2,2,,failed,No input,0.000,
+";
+        assert_eq!(written(Format::Csv), expected);
+    }
+
+    #[test]
+    fn markdown_escapes_cells() {
+        let expected = "\
+| Year | Day | Part | Answer | Status | Time (ms) | Input |
+| ---: | ---: | ---: | ---: | --- | ---: | --- |
+| 2024 | 1 | 1 | 11 | solved | 1.500 | cbf29ce484222325 |
+| 2024 | 1 | 2 |  | failed: a \"b\", c\\|d e | 1.500 | cbf29ce484222325 |
+| 2024 | 2 | 1 |  | failed: No input | 0.000 |  |
+| 2024 | 2 | 2 |  | failed: No input | 0.000 |  |
+";
+        assert_eq!(written(Format::Markdown), expected);
+        assert_eq!(written(Format::Text), "");
+    }
+}
@@ -0,0 +1,200 @@
+//! A signed grid position or offset, `y` down and `x` right like the indices
+//! of a [`Grid`](crate::Grid).
+//!
+//! Points add, subtract and scale like vectors. With the `simd` feature (and a
+//! nightly compiler) the arithmetic goes through `std::simd`, otherwise it is
+//! plain scalar code.
+
+use std::ops::{AddAssign, Neg, Sub, SubAssign};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub y: isize,
+    pub x: isize,
+}
+
+impl Point {
+    pub const UP: Point = Point::new(-1, 0);
+    pub const DOWN: Point = Point::new(1, 0);
+    pub const LEFT: Point = Point::new(0, -1);
+    pub const RIGHT: Point = Point::new(0, 1);
+    pub const ORTHOGONAL: [Point; 4] = [Point::UP, Point::DOWN, Point::LEFT, Point::RIGHT];
+
+    pub const fn new(y: isize, x: isize) -> Self {
+        Self { y, x }
+    }
+
+    /// The point at the grid indices `(y, x)`.
+    pub fn from_indices((y, x): (usize, usize)) -> Self {
+        Self::new(y as isize, x as isize)
+    }
+
+    /// The grid indices `(y, x)`, `None` left of or above the grid.
+    pub fn to_indices(self) -> Option<(usize, usize)> {
+        Some((self.y.try_into().ok()?, self.x.try_into().ok()?))
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((y, x): (isize, isize)) -> Self {
+        Self::new(y, x)
+    }
+}
+
+impl TryFrom<Point> for (usize, usize) {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(point: Point) -> Result<Self, Self::Error> {
+        Ok((point.y.try_into()?, point.x.try_into()?))
+    }
+}
+
+#[cfg(not(feature = "simd"))]
+mod scalar {
+    use std::ops::{Add, Mul};
+
+    use super::Point;
+
+    impl Add for Point {
+        type Output = Point;
+
+        fn add(self, rhs: Point) -> Point {
+            Point::new(self.y + rhs.y, self.x + rhs.x)
+        }
+    }
+
+    impl Mul<isize> for Point {
+        type Output = Point;
+
+        fn mul(self, rhs: isize) -> Point {
+            Point::new(self.y * rhs, self.x * rhs)
+        }
+    }
+}
+
+#[cfg(feature = "simd")]
+mod simd {
+    use std::{
+        ops::{Add, Mul},
+        simd::isizex2,
+    };
+
+    use super::Point;
+
+    impl From<Point> for isizex2 {
+        fn from(point: Point) -> Self {
+            isizex2::from_array([point.y, point.x])
+        }
+    }
+
+    impl From<isizex2> for Point {
+        fn from(lanes: isizex2) -> Self {
+            let [y, x] = lanes.to_array();
+            Point::new(y, x)
+        }
+    }
+
+    impl Add for Point {
+        type Output = Point;
+
+        fn add(self, rhs: Point) -> Point {
+            (isizex2::from(self) + isizex2::from(rhs)).into()
+        }
+    }
+
+    impl Mul<isize> for Point {
+        type Output = Point;
+
+        fn mul(self, rhs: isize) -> Point {
+            (isizex2::from(self) * isizex2::splat(rhs)).into()
+        }
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        self * -1
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        self + -rhs
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(2, -3);
+        let b = Point::new(-1, 5);
+        assert_eq!(a + b, Point::new(1, 2));
+        assert_eq!(a - b, Point::new(3, -8));
+        assert_eq!(a * 3, Point::new(6, -9));
+        assert_eq!(-a, Point::new(-2, 3));
+
+        let mut c = a;
+        c += b;
+        assert_eq!(c, a + b);
+        c -= b;
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    fn neighbours() {
+        let neighbours = Point::ORTHOGONAL.map(|dir| Point::new(0, 0) + dir);
+        assert_eq!(
+            neighbours,
+            [(-1, 0), (1, 0), (0, -1), (0, 1)].map(Point::from)
+        );
+        assert_eq!(
+            Point::ORTHOGONAL
+                .iter()
+                .copied()
+                .fold(Point::default(), |a, b| a + b),
+            Point::default()
+        );
+        assert_eq!(-Point::UP, Point::DOWN);
+        assert_eq!(-Point::LEFT, Point::RIGHT);
+    }
+
+    #[test]
+    fn indices() {
+        assert_eq!(Point::from_indices((3, 4)), Point::new(3, 4));
+        assert_eq!(Point::new(3, 4).to_indices(), Some((3, 4)));
+        assert_eq!(Point::new(-1, 4).to_indices(), None);
+        assert_eq!(Point::new(3, -1).to_indices(), None);
+        assert!(<(usize, usize)>::try_from(Point::new(0, -2)).is_err());
+    }
+
+    #[test]
+    fn bounds_check() {
+        let grid = Grid(vec![vec![0; 3]; 2]);
+        assert!(grid.bounds_check(Point::new(0, 0)));
+        assert!(grid.bounds_check(Point::new(1, 2)));
+        assert!(!grid.bounds_check(Point::new(2, 0)));
+        assert!(!grid.bounds_check(Point::new(0, 3)));
+        assert!(!grid.bounds_check(Point::new(-1, 0)));
+        assert!(!grid.bounds_check(Point::new(0, -1)));
+    }
+}
@@ -0,0 +1,190 @@
+//! Progress bars for long running loops.
+//!
+//! ```ignore
+//! use aoc_core::progress::{ParallelProgressExt, ProgressExt};
+//!
+//! candidates.iter().progress("loop check").filter(check_loop).count();
+//! numbers.par_iter().progress("try_solve").map(try_solve).sum();
+//! ```
+//!
+//! The bars are drawn by the `IndicatifLayer` installed in [`crate::init_tracing`].
+//! Without it (or with `AOC_PROGRESS=0`) the adapters just pass items through.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use indicatif::ProgressStyle;
+use rayon::iter::ParallelIterator;
+use tracing::Span;
+use tracing_indicatif::span_ext::IndicatifSpanExt;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Creates a span carrying a progress bar of `len` steps, or `None` if progress
+/// bars are disabled.
+///
+/// The bar is shown right away instead of on first enter, so it can be advanced
+/// from other threads with [`IndicatifSpanExt::pb_inc`].
+pub fn progress_span(name: &'static str, len: Option<usize>) -> Option<Span> {
+    if !enabled() {
+        return None;
+    }
+    let span = tracing::info_span!("progress", task = name);
+    let style = ProgressStyle::default_bar()
+        .template("{span_child_prefix}{span_fields} {elapsed} {bar:24} {pos}/{len}")
+        .expect("progress template is valid");
+    span.pb_set_style(&style);
+    if let Some(len) = len {
+        span.pb_set_length(len as u64);
+    }
+    span.pb_start();
+    Some(span)
+}
+
+/// Iterator adapter returned by [`ProgressExt::progress`].
+#[derive(Debug)]
+pub struct Progress<I> {
+    iter: I,
+    span: Option<Span>,
+    len: usize,
+    pos: usize,
+}
+
+impl<I> Progress<I> {
+    /// The length of the bar, taken from the `size_hint` at the start.
+    pub fn length(&self) -> usize {
+        self.len
+    }
+
+    /// The number of items passed through so far.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// The span drawing the bar, `None` when progress bars are disabled.
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+}
+
+impl<I: Iterator> Iterator for Progress<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        self.pos += 1;
+        if let Some(span) = &self.span {
+            span.pb_inc(1);
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+pub trait ProgressExt: Iterator + Sized {
+    /// Advances a progress bar named `name` for every item. The length is
+    /// taken from the upper bound of the `size_hint`.
+    fn progress(self, name: &'static str) -> Progress<Self> {
+        let (lower, upper) = self.size_hint();
+        let len = upper.unwrap_or(lower);
+        Progress {
+            span: progress_span(name, Some(len)),
+            iter: self,
+            len,
+            pos: 0,
+        }
+    }
+}
+
+impl<I: Iterator> ProgressExt for I {}
+
+pub trait ParallelProgressExt: ParallelIterator {
+    /// Advances a progress bar named `name` for every item, from whichever
+    /// rayon worker produced it. Indexed iterators get a bar with a length.
+    ///
+    /// The bar span is moved into the closure instead of relying on
+    /// `Span::current()`, which is not inherited by rayon's worker threads.
+    fn progress(self, name: &'static str) -> impl ParallelIterator<Item = Self::Item> {
+        let span = progress_span(name, self.opt_len());
+        self.map(move |item| {
+            if let Some(span) = &span {
+                span.pb_inc(1);
+            }
+            item
+        })
+    }
+}
+
+impl<P: ParallelIterator> ParallelProgressExt for P {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use rayon::prelude::*;
+    use tracing_indicatif::IndicatifLayer;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+
+    /// Tests switching [`ENABLED`] take turns.
+    static SWITCH: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn disabled_bars_have_no_span() {
+        let _switch = SWITCH.lock().unwrap();
+        set_enabled(false);
+        assert!(progress_span("off", Some(3)).is_none());
+        let mut progress = (0..3).progress("off");
+        assert!(progress.span().is_none());
+        assert_eq!(progress.by_ref().sum::<i32>(), 3);
+        assert_eq!(progress.position(), 3);
+    }
+
+    #[test]
+    fn no_op_without_an_indicatif_layer() {
+        let _switch = SWITCH.lock().unwrap();
+        set_enabled(true);
+        // no subscriber at all, then one without the indicatif layer
+        assert!(progress_span("bare", Some(3)).unwrap().is_disabled());
+        tracing::subscriber::with_default(tracing_subscriber::registry(), || {
+            let mut progress = [1, 2, 3].into_iter().progress("plain");
+            assert_eq!(progress.by_ref().sum::<i32>(), 6);
+            assert_eq!((progress.length(), progress.position()), (3, 3));
+            assert_eq!(
+                (0..100).into_par_iter().progress("plain").sum::<i32>(),
+                4950
+            );
+        });
+        set_enabled(false);
+    }
+
+    #[test]
+    fn span_length_and_position() {
+        let _switch = SWITCH.lock().unwrap();
+        set_enabled(true);
+        let subscriber = tracing_subscriber::registry().with(IndicatifLayer::new());
+        tracing::subscriber::with_default(subscriber, || {
+            let mut progress = (0..10).filter(|n| n % 2 == 0).progress("evens");
+            assert!(!progress.span().unwrap().is_disabled());
+            // the upper bound of the size hint
+            assert_eq!((progress.length(), progress.position()), (10, 0));
+            progress.next();
+            progress.next();
+            assert_eq!(progress.position(), 2);
+            assert_eq!(progress.count(), 3);
+            assert_eq!((0..100).into_par_iter().progress("sum").sum::<i32>(), 4950);
+        });
+        set_enabled(false);
+    }
+}
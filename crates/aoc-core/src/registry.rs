@@ -0,0 +1,56 @@
+//! The solvers of every year, keyed by `(year, day)`.
+//!
+//! Each year crate exports a `SOLUTIONS` slice, a binary that serves several
+//! years chains them:
+//!
+//! ```ignore
+//! let registry = Registry::new()
+//!     .with(aoc2023::days::SOLUTIONS)
+//!     .with(aoc2024::days::SOLUTIONS);
+//! ```
+
+use std::collections::BTreeMap;
+
+use crate::solution::Solver;
+
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    solvers: BTreeMap<(u16, u8), &'static dyn Solver>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `solvers`, replacing any solver already registered for the same day.
+    pub fn with(mut self, solvers: &[&'static dyn Solver]) -> Self {
+        for &solver in solvers {
+            self.solvers.insert((solver.year(), solver.day()), solver);
+        }
+        self
+    }
+
+    pub fn get(&self, year: u16, day: u8) -> Option<&'static dyn Solver> {
+        self.solvers.get(&(year, day)).copied()
+    }
+
+    /// The solvers of `year`, by day.
+    pub fn year(&self, year: u16) -> Vec<&'static dyn Solver> {
+        self.solvers
+            .range((year, 0)..=(year, u8::MAX))
+            .map(|(_, &solver)| solver)
+            .collect()
+    }
+
+    pub fn years(&self) -> Vec<u16> {
+        let mut years: Vec<u16> = self.solvers.keys().map(|&(year, _)| year).collect();
+        years.dedup();
+        years
+    }
+
+    /// The year commands default to when none is given.
+    pub fn latest_year(&self) -> Option<u16> {
+        self.solvers.keys().next_back().map(|&(year, _)| year)
+    }
+}
@@ -0,0 +1,283 @@
+//! HTTP client for an Advent of Code compatible server.
+//!
+//! Configured from the environment:
+//! - `AOC_SESSION`, the `session` cookie of a logged in browser. When it is not
+//!   set, it is read from `~/.config/aoc/session`.
+//! - `AOC_BASE_URL`, defaults to `https://adventofcode.com`. Point it at a local
+//!   stub server to try things out without touching the real site.
+//! - `AOC_THROTTLE`, the minimum number of seconds between two requests,
+//!   defaults to 5. The time of the last request is kept next to the inputs,
+//!   so separate invocations are throttled as well.
+
+use std::{
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{bail, Context};
+use ureq::Agent;
+
+use crate::input;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_THROTTLE: Duration = Duration::from_secs(5);
+const USER_AGENT: &str = "github.com/wyvernbw/advent-of-code-2024 (aoc CLI)";
+
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    throttle: Duration,
+    /// Where the time of the last request is kept.
+    state_dir: PathBuf,
+}
+
+impl Client {
+    pub fn new(base_url: impl Into<String>, session: impl Into<String>) -> Self {
+        let agent = Agent::config_builder()
+            // error pages are read like any other response
+            .http_status_as_error(false)
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+        Self {
+            agent,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            session: session.into().trim().to_string(),
+            throttle: DEFAULT_THROTTLE,
+            state_dir: input::dir(),
+        }
+    }
+
+    pub fn with_throttle(self, throttle: Duration) -> Self {
+        Self { throttle, ..self }
+    }
+
+    /// Keeps the time of the last request in `state_dir` instead of the
+    /// inputs directory.
+    pub fn with_state_dir(self, state_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            state_dir: state_dir.into(),
+            ..self
+        }
+    }
+
+    pub fn from_env() -> anyhow::Result<Self> {
+        let session = match std::env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => {
+                let path = session_path().context("Set AOC_SESSION or HOME")?;
+                std::fs::read_to_string(&path).with_context(|| {
+                    format!(
+                        "No session, set AOC_SESSION or write it to {}",
+                        path.display()
+                    )
+                })?
+            }
+        };
+        let base_url =
+            std::env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        let throttle = match std::env::var("AOC_THROTTLE") {
+            Ok(secs) => Duration::try_from_secs_f64(secs.parse()?)
+                .context("AOC_THROTTLE is not a number of seconds")?,
+            Err(_) => DEFAULT_THROTTLE,
+        };
+        Ok(Self::new(base_url, session).with_throttle(throttle))
+    }
+
+    /// URL of `path` below the puzzle page of `day`, like `.../2024/day/7/input`.
+    pub fn day_url(&self, year: u16, day: u8, path: &str) -> String {
+        format!("{}/{year}/day/{day}{path}", self.base_url)
+    }
+
+    /// GETs `url`, failing on anything but `200 OK`.
+    pub fn get(&self, url: &str) -> anyhow::Result<String> {
+        self.wait();
+        tracing::debug!(url, "GET");
+        let mut response = self
+            .agent
+            .get(url)
+            .header("Cookie", &format!("session={}", self.session))
+            .call()
+            .with_context(|| format!("GET {url} failed"))?;
+        let status = response.status();
+        let body = response.body_mut().read_to_string()?;
+        if !status.is_success() {
+            bail!("GET {url} returned {status}: {}", excerpt(&body));
+        }
+        Ok(body)
+    }
+
+    /// POSTs a form to `url` and returns the body of the response.
+    pub fn post_form(&self, url: &str, form: &[(&str, &str)]) -> anyhow::Result<String> {
+        self.wait();
+        tracing::debug!(url, "POST");
+        let mut response = self
+            .agent
+            .post(url)
+            .header("Cookie", &format!("session={}", self.session))
+            .send_form(form.iter().copied())
+            .with_context(|| format!("POST {url} failed"))?;
+        let status = response.status();
+        let body = response.body_mut().read_to_string()?;
+        if !status.is_success() {
+            bail!("POST {url} returned {status}: {}", excerpt(&body));
+        }
+        Ok(body)
+    }
+
+    /// Sleeps until the throttle time has passed since the last request, then
+    /// records this one.
+    fn wait(&self) {
+        let stamp = self.state_dir.join(".last-request");
+        let _ = std::fs::create_dir_all(&self.state_dir);
+        let since_last = std::fs::metadata(&stamp)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|last| SystemTime::now().duration_since(last).ok());
+        if let Some(remaining) = since_last.and_then(|since| self.throttle.checked_sub(since)) {
+            tracing::info!("waiting {remaining:.1?} before the next request");
+            thread::sleep(remaining);
+        }
+        if let Err(e) = std::fs::write(&stamp, "") {
+            tracing::warn!("could not write {}: {e}", stamp.display());
+        }
+    }
+}
+
+fn session_path() -> Option<PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config.join("aoc").join("session"))
+}
+
+/// The first line of an error page, which is all the site sends for most errors.
+fn excerpt(body: &str) -> String {
+    let line = body.trim().lines().next().unwrap_or_default();
+    match line.char_indices().nth(200) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+/// A local server standing in for the site in tests.
+#[cfg(test)]
+pub(crate) mod stub {
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+        time::Instant,
+    };
+
+    use tiny_http::{Response, Server};
+
+    /// A request the stub received.
+    #[derive(Debug, Clone)]
+    pub struct Seen {
+        pub url: String,
+        pub cookie: Option<String>,
+        pub at: Instant,
+    }
+
+    pub struct Stub {
+        pub url: String,
+        seen: Arc<Mutex<Vec<Seen>>>,
+    }
+
+    impl Stub {
+        /// Answers every request with the status and body from `respond`.
+        pub fn start(respond: impl Fn(&Seen) -> (u16, String) + Send + 'static) -> Self {
+            let server = Server::http("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+            let seen = Arc::new(Mutex::new(vec![]));
+            let log = seen.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    let cookie = request
+                        .headers()
+                        .iter()
+                        .find(|header| header.field.equiv("Cookie"))
+                        .map(|header| header.value.to_string());
+                    let request_seen = Seen {
+                        url: request.url().to_string(),
+                        cookie,
+                        at: Instant::now(),
+                    };
+                    let (status, body) = respond(&request_seen);
+                    log.lock().unwrap().push(request_seen);
+                    let _ = request.respond(Response::from_string(body).with_status_code(status));
+                }
+            });
+            Self { url, seen }
+        }
+
+        pub fn seen(&self) -> Vec<Seen> {
+            self.seen.lock().unwrap().clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stub::Stub, *};
+    use crate::temp_dir;
+
+    const LOG_IN: &str = "Puzzle inputs differ by user.  Please log in to get your puzzle input.";
+
+    fn client(stub: &Stub, session: &str, state_dir: PathBuf) -> Client {
+        Client::new(&stub.url, session)
+            .with_throttle(Duration::ZERO)
+            .with_state_dir(state_dir)
+    }
+
+    #[test]
+    fn sends_the_session_cookie() {
+        let stub = Stub::start(|_| (200, "1 2 3\n".to_string()));
+        let client = client(&stub, " secret\n", temp_dir("cookie"));
+        let body = client.get(&client.day_url(2024, 1, "/input")).unwrap();
+        assert_eq!(body, "1 2 3\n");
+        let seen = stub.seen();
+        assert_eq!(seen[0].url, "/2024/day/1/input");
+        assert_eq!(seen[0].cookie.as_deref(), Some("session=secret"));
+    }
+
+    #[test]
+    fn bad_session_is_an_error() {
+        let stub = Stub::start(|seen| match seen.cookie.as_deref() {
+            Some("session=good") => (200, "ok".to_string()),
+            _ => (400, format!("{LOG_IN}\n<p>more html</p>\n")),
+        });
+        let client = client(&stub, "expired", temp_dir("session"));
+        let url = client.day_url(2024, 1, "/input");
+        let error = client.get(&url).unwrap_err().to_string();
+        assert_eq!(
+            error,
+            format!("GET {url} returned 400 Bad Request: {LOG_IN}")
+        );
+    }
+
+    #[test]
+    fn throttles_requests_across_clients() {
+        let stub = Stub::start(|_| (200, String::new()));
+        let state_dir = temp_dir("throttle");
+        let throttle = Duration::from_millis(300);
+        for _ in 0..2 {
+            let client = client(&stub, "secret", state_dir.clone()).with_throttle(throttle);
+            client.get(&client.day_url(2024, 1, "/input")).unwrap();
+        }
+        let seen = stub.seen();
+        // file times are coarse, allow for the stamp looking a bit older
+        assert!(seen[1].at.duration_since(seen[0].at) >= throttle * 2 / 3);
+        assert!(state_dir.join(".last-request").exists());
+    }
+
+    #[test]
+    fn excerpt_keeps_the_first_line() {
+        assert_eq!(excerpt("\n  first\nsecond"), "first");
+        let long = "x".repeat(300);
+        assert_eq!(excerpt(&long), format!("{}...", "x".repeat(200)));
+    }
+}
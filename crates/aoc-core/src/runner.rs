@@ -0,0 +1,289 @@
+//! Runs registered solvers, timing each part and enforcing time limits.
+//!
+//! Every part runs on its own thread. When a part is still running after
+//! [`RunOptions::timeout`], its [`Cancel`] token is flagged and the part is
+//! reported as timed out; the runner moves on without waiting for the thread,
+//! which stops the next time the solver checks the token.
+
+use std::{
+    fmt::Display,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use indicatif::HumanBytes;
+
+use crate::{
+    alloc::{self, AllocStats},
+    cancel::{Cancel, Cancelled},
+    input,
+    solution::{Answer, Parsed, Part, Solver},
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Time limit for each part. Parsing is not limited.
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Solved(Answer),
+    Failed(String),
+    TimedOut,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Solved(answer) => write!(f, "{answer}"),
+            Status::Failed(error) => write!(f, "failed: {error}"),
+            Status::TimedOut => write!(f, "timed out"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub status: Status,
+    pub elapsed: Duration,
+    pub alloc: Option<AllocStats>,
+}
+
+impl PartReport {
+    pub fn answer(&self) -> Option<&Answer> {
+        match &self.status {
+            Status::Solved(answer) => Some(answer),
+            _ => None,
+        }
+    }
+
+    pub fn log(&self) {
+        let Self {
+            year: _,
+            day,
+            part,
+            status,
+            elapsed,
+            alloc,
+        } = self;
+        let part = part.to_string();
+        match alloc {
+            Some(stats) => tracing::info!(
+                day,
+                part,
+                %status,
+                ?elapsed,
+                allocations = stats.allocations,
+                bytes = %HumanBytes(stats.bytes as u64),
+                peak = %HumanBytes(stats.peak as u64),
+                "🔥"
+            ),
+            None => tracing::info!(day, part, %status, ?elapsed, "🔥"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub year: u16,
+    pub day: u8,
+    /// [`input::hash`] of the input, if it could be read.
+    pub input_hash: Option<String>,
+    /// Time spent parsing, or the error that stopped the day before solving.
+    pub parse: Result<Duration, String>,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    /// Whether the input was parsed and every part produced an answer.
+    pub fn solved(&self) -> bool {
+        self.parse.is_ok() && self.parts.iter().all(|part| part.answer().is_some())
+    }
+}
+
+/// Loads the input of `solver` from [`input::load`] and runs both parts.
+pub fn run_day(solver: &'static dyn Solver, options: &RunOptions) -> DayReport {
+    let (year, day) = (solver.year(), solver.day());
+    match input::load(year, day) {
+        Ok(input) => run_day_with_input(solver, &input, options),
+        Err(e) => {
+            tracing::error!(year, day, "{e:#}");
+            DayReport {
+                year,
+                day,
+                input_hash: None,
+                parse: Err(format!("{e:#}")),
+                parts: vec![],
+            }
+        }
+    }
+}
+
+pub fn run_day_with_input(
+    solver: &'static dyn Solver,
+    input: &str,
+    options: &RunOptions,
+) -> DayReport {
+    let (year, day) = (solver.year(), solver.day());
+    let input_hash = Some(input::hash(input));
+    let _span = tracing::info_span!("day", year, day).entered();
+    let start = Instant::now();
+    let parsed = match solver.parse(input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            tracing::error!(day, "parse failed: {e:#}");
+            return DayReport {
+                year,
+                day,
+                input_hash,
+                parse: Err(format!("{e:#}")),
+                parts: vec![],
+            };
+        }
+    };
+    let parse = start.elapsed();
+    tracing::debug!(day, ?parse, "parsed");
+    let parts = Part::ALL
+        .into_iter()
+        .map(|part| {
+            let report = run_part(solver, &parsed, part, options);
+            report.log();
+            report
+        })
+        .collect();
+    DayReport {
+        year,
+        day,
+        input_hash,
+        parse: Ok(parse),
+        parts,
+    }
+}
+
+pub fn run_part(
+    solver: &'static dyn Solver,
+    parsed: &Parsed,
+    part: Part,
+    options: &RunOptions,
+) -> PartReport {
+    let (year, day) = (solver.year(), solver.day());
+    let cancel = Cancel::new();
+    let (tx, rx) = mpsc::channel();
+    let worker = {
+        let parsed = parsed.clone();
+        let cancel = cancel.clone();
+        let span = tracing::Span::current();
+        move || {
+            let _span = span.entered();
+            let _ = tx.send(solver.solve(&parsed, part, &cancel));
+        }
+    };
+    let ((received, elapsed), alloc) = alloc::measure(|| {
+        let start = Instant::now();
+        let spawned = thread::Builder::new()
+            .name(format!("day-{day}-{part}"))
+            .spawn(worker);
+        let received = match (spawned, options.timeout) {
+            (Err(e), _) => Ok(Err(e.into())),
+            (Ok(_), Some(timeout)) => rx.recv_timeout(timeout),
+            (Ok(_), None) => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        (received, start.elapsed())
+    });
+    let status = match received {
+        Ok(Ok(answer)) => Status::Solved(answer),
+        Ok(Err(e)) if e.chain().any(|cause| cause.is::<Cancelled>()) => Status::TimedOut,
+        Ok(Err(e)) => Status::Failed(format!("{e:#}")),
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            Status::TimedOut
+        }
+        Err(RecvTimeoutError::Disconnected) => Status::Failed("solver panicked".to_string()),
+    };
+    PartReport {
+        year,
+        day,
+        part,
+        status,
+        elapsed,
+        alloc,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::*;
+    use crate::solution::Solution;
+
+    /// Set once part 2 of [`Sleeper`] sees its token flagged.
+    static STOPPED: AtomicBool = AtomicBool::new(false);
+
+    /// Part 1 sleeps through its time limit without looking at its token,
+    /// part 2 naps until it is cancelled.
+    struct Sleeper;
+
+    impl Solution for Sleeper {
+        const YEAR: u16 = 2024;
+        const DAY: u8 = 1;
+        type Input = ();
+
+        fn parse(_: &str) -> anyhow::Result<Self::Input> {
+            Ok(())
+        }
+
+        fn part_1(_: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+            thread::sleep(Duration::from_secs(1));
+            Ok(1)
+        }
+
+        fn part_2(_: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+            for _ in 0..100 {
+                if let Err(cancelled) = cancel.check() {
+                    STOPPED.store(true, Ordering::Relaxed);
+                    return Err(cancelled.into());
+                }
+                thread::sleep(Duration::from_millis(10));
+            }
+            Ok(2)
+        }
+    }
+
+    fn run(part: Part, timeout: Option<Duration>) -> PartReport {
+        let parsed = Sleeper.parse("").unwrap();
+        run_part(&Sleeper, &parsed, part, &RunOptions { timeout })
+    }
+
+    #[test]
+    fn sleeping_past_the_limit_times_out() {
+        let report = run(Part::One, Some(Duration::from_millis(50)));
+        assert_eq!(report.status, Status::TimedOut);
+        assert!(report.elapsed < Duration::from_millis(500));
+    }
+
+    #[test]
+    fn timed_out_part_is_cancelled() {
+        let report = run(Part::Two, Some(Duration::from_millis(50)));
+        assert_eq!(report.status, Status::TimedOut);
+        let start = Instant::now();
+        while !STOPPED.load(Ordering::Relaxed) {
+            assert!(
+                start.elapsed() < Duration::from_secs(1),
+                "part 2 was not cancelled"
+            );
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn no_limit_waits_for_the_answer() {
+        let report = run(Part::One, None);
+        assert_eq!(report.status, Status::Solved(Answer("1".to_string())));
+    }
+}
@@ -0,0 +1,235 @@
+//! The states a solver goes through on a grid, recorded for `aoc explore` and
+//! exported by [`asciicast`](crate::asciicast).
+//!
+//! A [`Scene`] is the grid of a parsed input plus one [`Step`] per state,
+//! built by the days in [`Solution::record`](crate::Solution::record). A step
+//! highlights cells with [`Mark`]s: its own marks only show while the step is
+//! on screen, its trail stays for every later step, which keeps long walks
+//! cheap to record.
+
+use std::collections::HashMap;
+
+use crate::Grid;
+
+/// How a cell is drawn when nothing is marked on it, and what the explorer
+/// says about it under the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tile {
+    pub glyph: char,
+    pub value: String,
+}
+
+impl Tile {
+    pub fn new(glyph: char, value: impl Into<String>) -> Self {
+        Self {
+            glyph,
+            value: value.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Highlight {
+    /// Cells the solver has been to.
+    Visited,
+    /// Cells the solver is about to look at.
+    Frontier,
+    /// Where the solver is.
+    Current,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mark {
+    /// `(y, x)`, like [`Grid`] indices.
+    pub pos: (usize, usize),
+    pub highlight: Highlight,
+    /// Drawn instead of the glyph of the tile.
+    pub glyph: Option<char>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Step {
+    pub label: String,
+    pub marks: Vec<Mark>,
+    pub trail: Vec<Mark>,
+}
+
+impl Step {
+    pub fn mark(&mut self, pos: (usize, usize), highlight: Highlight) -> &mut Self {
+        self.marks.push(Mark {
+            pos,
+            highlight,
+            glyph: None,
+        });
+        self
+    }
+
+    pub fn mark_glyph(
+        &mut self,
+        pos: (usize, usize),
+        highlight: Highlight,
+        glyph: char,
+    ) -> &mut Self {
+        self.marks.push(Mark {
+            pos,
+            highlight,
+            glyph: Some(glyph),
+        });
+        self
+    }
+
+    /// Marks `pos` for this step and all the steps after it.
+    pub fn trail(&mut self, pos: (usize, usize), highlight: Highlight) -> &mut Self {
+        self.trail.push(Mark {
+            pos,
+            highlight,
+            glyph: None,
+        });
+        self
+    }
+
+    /// Marks `pos` for this step and all the steps after it, drawn as `glyph`,
+    /// for cells the solver changes.
+    pub fn trail_glyph(
+        &mut self,
+        pos: (usize, usize),
+        highlight: Highlight,
+        glyph: char,
+    ) -> &mut Self {
+        self.trail.push(Mark {
+            pos,
+            highlight,
+            glyph: Some(glyph),
+        });
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Scene {
+    tiles: Grid<Tile>,
+    steps: Vec<Step>,
+}
+
+impl Scene {
+    pub fn new(tiles: Grid<Tile>) -> Self {
+        Self {
+            tiles,
+            steps: vec![],
+        }
+    }
+
+    /// Starts a new step.
+    pub fn push(&mut self, label: impl Into<String>) -> &mut Step {
+        self.steps.push(Step {
+            label: label.into(),
+            ..Default::default()
+        });
+        self.steps.last_mut().unwrap()
+    }
+
+    pub fn tiles(&self) -> &Grid<Tile> {
+        &self.tiles
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn rows(&self) -> usize {
+        self.tiles.0.len()
+    }
+
+    pub fn columns(&self) -> usize {
+        self.tiles.0.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// The marks shown at `step`: the trails of every step up to it, with the
+    /// marks of `step` itself on top.
+    pub fn overlay(&self, step: usize) -> HashMap<(usize, usize), Mark> {
+        let Some(steps) = self.steps.get(..=step) else {
+            return HashMap::new();
+        };
+        let trails = steps.iter().flat_map(|step| &step.trail);
+        trails
+            .chain(&steps[step].marks)
+            .map(|&mark| (mark.pos, mark))
+            .collect()
+    }
+
+    /// The grid at `step`, one string per row. `paint` appends a cell to the
+    /// row given its glyph and highlight.
+    pub fn render(
+        &self,
+        step: usize,
+        mut paint: impl FnMut(&mut String, char, Option<Highlight>),
+    ) -> Vec<String> {
+        let overlay = self.overlay(step);
+        self.tiles
+            .0
+            .iter()
+            .enumerate()
+            .map(|(y, row)| {
+                let mut line = String::new();
+                for (x, tile) in row.iter().enumerate() {
+                    match overlay.get(&(y, x)) {
+                        Some(mark) => paint(
+                            &mut line,
+                            mark.glyph.unwrap_or(tile.glyph),
+                            Some(mark.highlight),
+                        ),
+                        None => paint(&mut line, tile.glyph, None),
+                    }
+                }
+                line
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 2x3 grid of dots, with a walk along the top row.
+    fn walk() -> Scene {
+        let tiles = Grid(vec![vec![Tile::new('.', "floor"); 3]; 2]);
+        let mut scene = Scene::new(tiles);
+        for x in 0..3 {
+            scene
+                .push(format!("at {x}"))
+                .trail((0, x), Highlight::Visited)
+                .mark_glyph((0, x), Highlight::Current, '@');
+        }
+        scene
+            .push("done")
+            .trail_glyph((1, 1), Highlight::Frontier, '#');
+        scene
+    }
+
+    /// Visited cells as `v`, other marks as their glyph.
+    fn plain(line: &mut String, glyph: char, highlight: Option<Highlight>) {
+        line.push(match highlight {
+            Some(Highlight::Visited) => 'v',
+            _ => glyph,
+        });
+    }
+
+    #[test]
+    fn marks_show_on_their_step_and_trails_stay() {
+        let scene = walk();
+        assert_eq!((scene.rows(), scene.columns()), (2, 3));
+        assert_eq!(scene.render(0, plain), ["@..", "..."]);
+        assert_eq!(scene.render(2, plain), ["vv@", "..."]);
+        assert_eq!(scene.render(3, plain), ["vvv", ".#."]);
+    }
+
+    #[test]
+    fn overlay_past_the_end_is_empty() {
+        let scene = walk();
+        assert_eq!(scene.overlay(3).len(), 4);
+        assert!(scene.overlay(4).is_empty());
+        assert_eq!(scene.render(4, plain), ["...", "..."]);
+        assert_eq!(scene.steps()[1].label, "at 1");
+    }
+}
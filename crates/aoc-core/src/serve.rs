@@ -0,0 +1,334 @@
+//! `aoc serve`: a local HTTP server in front of a [`Registry`].
+//!
+//! `POST /years/{y}/days/{n}/parts/{p}` solves the input in the request body
+//! and answers with the JSON of a [`Record`] plus the time spent parsing.
+//! `GET /years/{y}/days` lists the solved days. Both also work without the
+//! `/years/{y}` prefix, for the year the server was started with. Errors are
+//! `{"error": "..."}` with a matching status code: 400 for an input that does
+//! not parse, 404 for an unknown route or day, 500 for a failed part and 504
+//! for a timeout.
+
+use std::{
+    io::Read,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use serde::Serialize;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{
+    input,
+    output::Record,
+    registry::Registry,
+    runner::{self, RunOptions, Status},
+    solution::Part,
+};
+
+/// Inputs are a few kilobytes, anything much larger is a mistake.
+const MAX_INPUT: u64 = 16 * 1024 * 1024;
+
+/// Time limit used when `aoc serve` is not given one, so that a stuck request
+/// still gets an answer.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Serialize)]
+struct Solved {
+    #[serde(flatten)]
+    record: Record,
+    parse_ms: f64,
+}
+
+/// What the server answers with.
+#[derive(Debug, Clone)]
+pub struct Service {
+    pub registry: Registry,
+    /// The year of routes without a `/years/{y}` prefix.
+    pub year: u16,
+    pub options: RunOptions,
+}
+
+/// Serves requests on `addr` until the process is stopped. Every request is
+/// handled on its own thread, so a slow day does not hold up the others.
+pub fn serve(addr: &str, service: Service) -> anyhow::Result<()> {
+    let server =
+        Server::http(addr).map_err(|e| anyhow::anyhow!("Could not listen on {addr}: {e}"))?;
+    tracing::info!("listening on http://{}", server.server_addr());
+    run(server, service)
+}
+
+/// Answers the requests of `server` until it is closed.
+fn run(server: Server, service: Service) -> anyhow::Result<()> {
+    let service = Arc::new(service);
+    for request in server.incoming_requests() {
+        let service = service.clone();
+        thread::Builder::new()
+            .name("serve".to_string())
+            .spawn(move || respond(request, &service))
+            .context("Could not spawn a request thread")?;
+    }
+    Ok(())
+}
+
+fn respond(mut request: Request, service: &Service) {
+    let method = request.method().clone();
+    let url = request.url().to_string();
+    let mut body = String::new();
+    let (status, json) = match request
+        .as_reader()
+        .take(MAX_INPUT)
+        .read_to_string(&mut body)
+    {
+        Ok(_) => service.handle(&method, &url, &body),
+        Err(e) => error(400, format!("Could not read the body: {e}")),
+    };
+    tracing::info!(%method, url, status);
+    let response = Response::from_string(json)
+        .with_status_code(status)
+        .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+    if let Err(e) = request.respond(response) {
+        tracing::warn!(url, "could not respond: {e}");
+    }
+}
+
+impl Service {
+    /// Routes one request, returning the status code and the JSON body.
+    pub fn handle(&self, method: &Method, url: &str, body: &str) -> (u16, String) {
+        let path: Vec<&str> = url
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+        let (year, path) = match path.as_slice() {
+            ["years", year, path @ ..] => match year.parse() {
+                Ok(year) => (year, path),
+                Err(_) => return error(404, format!("Unknown year `{year}`")),
+            },
+            path => (self.year, path),
+        };
+        match (method, path) {
+            (Method::Get, ["days"]) => {
+                let days: Vec<u8> = self
+                    .registry
+                    .year(year)
+                    .iter()
+                    .map(|solver| solver.day())
+                    .collect();
+                (
+                    200,
+                    serde_json::json!({ "year": year, "days": days }).to_string(),
+                )
+            }
+            (Method::Post, ["days", day, "parts", part]) => {
+                let Some(part) = part.parse().ok().and_then(Part::from_number) else {
+                    return error(404, format!("Unknown part `{part}`, expected 1 or 2"));
+                };
+                let Some(solver) = day
+                    .parse()
+                    .ok()
+                    .and_then(|day| self.registry.get(year, day))
+                else {
+                    return error(404, format!("Day `{day}` of {year} is not solved"));
+                };
+                let start = Instant::now();
+                let parsed = match solver.parse(body) {
+                    Ok(parsed) => parsed,
+                    Err(e) => return error(400, format!("{e:#}")),
+                };
+                let parse = start.elapsed();
+                let report = runner::run_part(solver, &parsed, part, &self.options);
+                let status = match report.status {
+                    Status::Solved(_) => 200,
+                    Status::Failed(_) => 500,
+                    Status::TimedOut => 504,
+                };
+                let solved = Solved {
+                    record: Record::from_part(&report, Some(input::hash(body))),
+                    parse_ms: parse.as_secs_f64() * 1000.0,
+                };
+                (status, serde_json::to_string(&solved).unwrap_or_default())
+            }
+            (_, ["days"] | ["days", _, "parts", _]) => {
+                error(405, format!("{method} is not allowed"))
+            }
+            _ => error(404, format!("No route for `{url}`")),
+        }
+    }
+}
+
+fn error(status: u16, message: String) -> (u16, String) {
+    (status, serde_json::json!({ "error": message }).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        net::{SocketAddr, TcpStream},
+    };
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{
+        cancel::Cancel,
+        solution::{Answer, Solution},
+        validate::{self, Diagnostic},
+    };
+
+    /// Sums one number per line in part 1, part 2 always fails.
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: u16 = 2024;
+        const DAY: u8 = 1;
+        type Input = Vec<u64>;
+
+        fn validate(input: &str) -> Vec<Diagnostic> {
+            validate::lines(input)
+                .filter(|(_, line)| line.parse::<u64>().is_err())
+                .map(|(y, line)| Diagnostic::new(y, 1, format!("{line:?} is not a number")))
+                .collect()
+        }
+
+        fn parse(input: &str) -> anyhow::Result<Self::Input> {
+            Ok(input
+                .lines()
+                .map(|line| line.parse())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part_1(input: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+            Ok(input.iter().sum::<u64>())
+        }
+
+        fn part_2(_: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+            Err::<u64, _>(anyhow::anyhow!("No part 2"))
+        }
+    }
+
+    fn service() -> Service {
+        Service {
+            registry: Registry::new().with(&[&Sum]),
+            year: 2024,
+            options: RunOptions::default(),
+        }
+    }
+
+    fn handle(method: Method, url: &str, body: &str) -> (u16, Value) {
+        let (status, json) = service().handle(&method, url, body);
+        (status, serde_json::from_str(&json).unwrap())
+    }
+
+    #[test]
+    fn lists_days() {
+        for url in ["/days", "/years/2024/days"] {
+            let (status, json) = handle(Method::Get, url, "");
+            assert_eq!(status, 200);
+            assert_eq!(json, serde_json::json!({ "year": 2024, "days": [1] }));
+        }
+        let (status, json) = handle(Method::Get, "/years/2023/days", "");
+        assert_eq!(status, 200);
+        assert_eq!(json["days"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn solves_a_posted_input() {
+        for url in ["/days/1/parts/1", "/years/2024/days/1/parts/1?verbose"] {
+            let (status, json) = handle(Method::Post, url, "1\n2\n3\n");
+            assert_eq!(status, 200);
+            assert_eq!(json["answer"], "6");
+            assert_eq!(json["status"], "solved");
+            assert_eq!(json["input_hash"], input::hash("1\n2\n3\n"));
+            assert!(json["parse_ms"].is_number());
+        }
+    }
+
+    #[test]
+    fn failed_part_is_a_server_error() {
+        let (status, json) = handle(Method::Post, "/days/1/parts/2", "1\n");
+        assert_eq!(status, 500);
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["error"], "No part 2");
+    }
+
+    #[test]
+    fn unknown_day_part_or_route() {
+        let not_found = [
+            ("/days/2/parts/1", "Day `2` of 2024 is not solved"),
+            ("/days/x/parts/1", "Day `x` of 2024 is not solved"),
+            (
+                "/years/2023/days/1/parts/1",
+                "Day `1` of 2023 is not solved",
+            ),
+            ("/days/1/parts/3", "Unknown part `3`, expected 1 or 2"),
+            ("/years/next/days", "Unknown year `next`"),
+            ("/answers", "No route for `/answers`"),
+        ];
+        for (url, message) in not_found {
+            let (status, json) = handle(Method::Post, url, "1\n");
+            assert_eq!(
+                (status, json["error"].as_str()),
+                (404, Some(message)),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn wrong_method() {
+        let (status, json) = handle(Method::Get, "/days/1/parts/1", "");
+        assert_eq!(status, 405);
+        assert_eq!(json["error"], "GET is not allowed");
+        let (status, _) = handle(Method::Delete, "/days", "");
+        assert_eq!(status, 405);
+    }
+
+    #[test]
+    fn bad_input_body() {
+        let (status, json) = handle(Method::Post, "/days/1/parts/1", "1\ntwo\n3\n");
+        assert_eq!(status, 400);
+        let error = json["error"].as_str().unwrap();
+        assert!(error.contains("2:1: \"two\" is not a number"), "{error}");
+    }
+
+    /// Sends one request to the server at `addr`, returning the status code
+    /// and the JSON body.
+    fn request(addr: SocketAddr, method: &str, url: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "{method} {url} HTTP/1.0\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn real_server() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        thread::spawn(move || run(server, service()));
+
+        let (status, json) = request(addr, "GET", "/days", "");
+        assert_eq!(status, 200);
+        assert_eq!(json, serde_json::json!({ "year": 2024, "days": [1] }));
+
+        let (status, json) = request(addr, "POST", "/days/1/parts/1", "1\n2\n");
+        assert_eq!(status, 200);
+        assert_eq!(json["answer"], "3");
+
+        let (status, json) = request(addr, "GET", "/answers", "");
+        assert_eq!(status, 404);
+        assert_eq!(json["error"], "No route for `/answers`");
+    }
+}
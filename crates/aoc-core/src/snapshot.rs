@@ -0,0 +1,242 @@
+//! Golden files for rendered output: maps, disks, anything a day draws.
+//!
+//! [`check`] compares a rendering with `<name>.snap` in a snapshot directory,
+//! [`assert_snapshot!`](crate::assert_snapshot) does it for the `snapshots`
+//! directory of the calling crate and panics with a line diff on a mismatch.
+//! With `AOC_UPDATE_SNAPSHOTS=1` the renderings are written instead, to be
+//! reviewed in `git diff` like any other change.
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+/// Set to anything but `0` to write snapshots instead of checking them.
+pub const UPDATE_VAR: &str = "AOC_UPDATE_SNAPSHOTS";
+
+/// Lines kept around every change in a diff.
+const CONTEXT: usize = 2;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("No snapshot at {}, run with {UPDATE_VAR}=1 to create it", .0.display())]
+    Missing(PathBuf),
+    #[error(
+        "Snapshot {} does not match, run with {UPDATE_VAR}=1 to update it\n{diff}",
+        path.display()
+    )]
+    Mismatch { path: PathBuf, diff: String },
+    #[error("Could not access {}: {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+}
+
+/// Checks `actual` against the snapshot `name` in `dir`, or writes it there
+/// when [`UPDATE_VAR`] is set.
+pub fn check(dir: &Path, name: &str, actual: &str) -> Result<(), SnapshotError> {
+    let path = dir.join(format!("{name}.snap"));
+    let io_error = |source| SnapshotError::Io {
+        path: path.clone(),
+        source,
+    };
+    if std::env::var(UPDATE_VAR).is_ok_and(|value| value != "0") {
+        std::fs::create_dir_all(dir).map_err(io_error)?;
+        return std::fs::write(&path, actual).map_err(io_error);
+    }
+    let expected = match std::fs::read_to_string(&path) {
+        Ok(expected) => expected,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            return Err(SnapshotError::Missing(path));
+        }
+        Err(error) => return Err(io_error(error)),
+    };
+    if expected == actual {
+        return Ok(());
+    }
+    Err(SnapshotError::Mismatch {
+        diff: diff(&expected, actual),
+        path,
+    })
+}
+
+/// Panics unless the expression renders as the snapshot of that name in the
+/// `snapshots` directory of the crate.
+///
+/// ```ignore
+/// aoc_core::assert_snapshot!("day_9_disk", disk.to_string());
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr $(,)?) => {{
+        let dir = ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots");
+        if let Err(error) = $crate::snapshot::check(&dir, $name, &$actual) {
+            panic!("{error}");
+        }
+    }};
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// A line diff of `expected` to `actual`, with `-` for lines only in the
+/// snapshot and `+` for lines only in the rendering, in hunks headed by the
+/// line numbers they start at.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let lines = diff_lines(expected, actual);
+    let changed: Vec<usize> = (0..lines.len())
+        .filter(|&i| !matches!(lines[i], Line::Same(_)))
+        .collect();
+    let mut out = String::from("--- snapshot\n+++ rendered\n");
+    let (mut old, mut new) = (1, 1);
+    let mut shown = 0;
+    for (i, line) in lines.iter().enumerate() {
+        let near = |&c: &usize| c + CONTEXT >= i && c <= i + CONTEXT;
+        if changed.iter().any(near) {
+            if shown < i || i == 0 {
+                out += &format!("@@ line {old} -> {new} @@\n");
+            }
+            out += &match line {
+                Line::Same(line) => format!(" {line}\n"),
+                Line::Removed(line) => format!("-{line}\n"),
+                Line::Added(line) => format!("+{line}\n"),
+            };
+            shown = i + 1;
+        }
+        match line {
+            Line::Same(_) => (old, new) = (old + 1, new + 1),
+            Line::Removed(_) => old += 1,
+            Line::Added(_) => new += 1,
+        }
+    }
+    if expected.ends_with('\n') != actual.ends_with('\n') {
+        let ending = |s: &str| match s.ends_with('\n') {
+            true => "ends with a newline",
+            false => "has no newline at the end",
+        };
+        out += &format!(
+            "the snapshot {}, the rendering {}\n",
+            ending(expected),
+            ending(actual)
+        );
+    }
+    out
+}
+
+/// The lines of both sides along their longest common subsequence.
+fn diff_lines<'a>(expected: &'a str, actual: &'a str) -> Vec<Line<'a>> {
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = actual.lines().collect();
+    // common[i][j]: longest common subsequence of a[i..] and b[j..]
+    let mut common = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            common[i][j] = match a[i] == b[j] {
+                true => common[i + 1][j + 1] + 1,
+                false => common[i + 1][j].max(common[i][j + 1]),
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut lines = vec![];
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            lines.push(Line::Same(a[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if j == b.len() || (i < a.len() && common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(Line::Removed(a[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(b[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Tests reading or setting [`UPDATE_VAR`] take turns.
+    static ENV: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn changed_middle_line() {
+        let expected = "1\n2\n3\n4\n5\n6\n7\n";
+        let actual = "1\n2\n3\nfour\n5\n6\n7\n";
+        assert_eq!(
+            diff(expected, actual),
+            "--- snapshot\n+++ rendered\n@@ line 2 -> 2 @@\n 2\n 3\n-4\n+four\n 5\n 6\n"
+        );
+    }
+
+    #[test]
+    fn changed_first_line() {
+        assert_eq!(
+            diff("a\nb\nc\nd\n", "A\nb\nc\nd\n"),
+            "--- snapshot\n+++ rendered\n@@ line 1 -> 1 @@\n-a\n+A\n b\n c\n"
+        );
+    }
+
+    #[test]
+    fn trailing_lines() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nb\nc\nd\ne\n"),
+            "--- snapshot\n+++ rendered\n@@ line 2 -> 2 @@\n b\n c\n+d\n+e\n"
+        );
+        assert_eq!(
+            diff("a\nb\nc\nd\n", "a\nb\n"),
+            "--- snapshot\n+++ rendered\n@@ line 1 -> 1 @@\n a\n b\n-c\n-d\n"
+        );
+    }
+
+    #[test]
+    fn trailing_newline() {
+        assert_eq!(
+            diff("a\n", "a"),
+            "--- snapshot\n+++ rendered\nthe snapshot ends with a newline, the rendering has no newline at the end\n"
+        );
+    }
+
+    #[test]
+    fn missing_and_mismatched_snapshots() {
+        let _env = ENV.lock().unwrap();
+        let dir = crate::temp_dir("snapshot-check");
+        assert!(matches!(
+            check(&dir, "map", "#.\n"),
+            Err(SnapshotError::Missing(path)) if path == dir.join("map.snap")
+        ));
+
+        std::fs::write(dir.join("map.snap"), "#.\n").unwrap();
+        check(&dir, "map", "#.\n").unwrap();
+        match check(&dir, "map", ".#\n") {
+            Err(SnapshotError::Mismatch { path, diff }) => {
+                assert_eq!(path, dir.join("map.snap"));
+                assert!(diff.contains("-#.\n+.#\n"), "{diff}");
+            }
+            other => panic!("expected a mismatch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn update_writes_the_snapshot() {
+        let _env = ENV.lock().unwrap();
+        let dir = crate::temp_dir("snapshot-update").join("snapshots");
+        std::env::set_var(UPDATE_VAR, "1");
+        let written = check(&dir, "map", ".#\n");
+        std::env::remove_var(UPDATE_VAR);
+        written.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("map.snap")).unwrap(),
+            ".#\n"
+        );
+        check(&dir, "map", ".#\n").unwrap();
+    }
+}
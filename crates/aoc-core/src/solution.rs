@@ -0,0 +1,175 @@
+//! The [`Solution`] trait implemented by every day, and its object safe
+//! counterpart [`Solver`] used by the [registry](crate::registry).
+
+use std::{
+    any::Any,
+    fmt::{Debug, Display},
+    sync::Arc,
+};
+
+use anyhow::Context;
+
+use crate::{
+    cancel::Cancel,
+    generate::Rng,
+    inspect::Report,
+    scene::Scene,
+    validate::{Diagnostic, Invalid},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "part_{}", self.number())
+    }
+}
+
+/// A puzzle answer, kept as text so every day can use its own number type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Answer(pub String);
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+macro_rules! answer_from {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(value: $ty) -> Self {
+                    Answer(value.to_string())
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, String, &str);
+
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+    /// The parsed puzzle input, shared by both parts.
+    type Input: Send + Sync + 'static;
+
+    /// Lists everything wrong with `input` that would make [`Solution::parse`]
+    /// or the parts fail or panic. Runs before parsing.
+    fn validate(_input: &str) -> Vec<Diagnostic> {
+        vec![]
+    }
+    fn parse(input: &str) -> anyhow::Result<Self::Input>;
+    fn part_1(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>>;
+    fn part_2(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>>;
+
+    /// Adds day specific statistics about the input to `report`, for
+    /// `aoc inspect`.
+    fn inspect(_input: &Self::Input, _report: &mut Report) {}
+
+    /// Replays the solver on `input` step by step, for `aoc explore`. `None`
+    /// for days that are not solved on a grid.
+    fn record(_input: &Self::Input) -> Option<Scene> {
+        None
+    }
+
+    /// A random input that both parts solve without errors, for `aoc generate`
+    /// and tests. `size` scales it, each day says how, and is at least 1.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+/// Parses `input` and solves one part of it, mostly for tests.
+pub fn solve<S: Solution>(input: &str, part: Part) -> anyhow::Result<Answer> {
+    Invalid::check(S::validate(input))?;
+    let input = S::parse(input)?;
+    let cancel = Cancel::new();
+    match part {
+        Part::One => S::part_1(&input, &cancel).map(Into::into),
+        Part::Two => S::part_2(&input, &cancel).map(Into::into),
+    }
+}
+
+/// A parsed input whose type is only known to the [`Solver`] that parsed it.
+pub type Parsed = Arc<dyn Any + Send + Sync>;
+
+/// Object safe version of [`Solution`], implemented for every solution.
+pub trait Solver: Send + Sync {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    /// Validates and parses `input`, validation errors are an [`Invalid`].
+    fn parse(&self, input: &str) -> anyhow::Result<Parsed>;
+    fn solve(&self, input: &Parsed, part: Part, cancel: &Cancel) -> anyhow::Result<Answer>;
+    fn inspect(&self, input: &Parsed, report: &mut Report) -> anyhow::Result<()>;
+    fn record(&self, input: &Parsed) -> anyhow::Result<Option<Scene>>;
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String>;
+}
+
+impl Debug for dyn Solver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Solver({} day-{})", self.year(), self.day())
+    }
+}
+
+impl<S: Solution + Send + Sync> Solver for S {
+    fn year(&self) -> u16 {
+        S::YEAR
+    }
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+    fn parse(&self, input: &str) -> anyhow::Result<Parsed> {
+        Invalid::check(S::validate(input))?;
+        Ok(Arc::new(S::parse(input)?))
+    }
+    fn solve(&self, input: &Parsed, part: Part, cancel: &Cancel) -> anyhow::Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .context("Input was parsed by another day")?;
+        match part {
+            Part::One => S::part_1(input, cancel).map(Into::into),
+            Part::Two => S::part_2(input, cancel).map(Into::into),
+        }
+    }
+    fn inspect(&self, input: &Parsed, report: &mut Report) -> anyhow::Result<()> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .context("Input was parsed by another day")?;
+        S::inspect(input, report);
+        Ok(())
+    }
+    fn record(&self, input: &Parsed) -> anyhow::Result<Option<Scene>> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .context("Input was parsed by another day")?;
+        Ok(S::record(input))
+    }
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        S::generate(rng, size)
+    }
+}
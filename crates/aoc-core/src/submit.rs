@@ -0,0 +1,316 @@
+//! `aoc submit <DAY> <PART>`: posts an answer and records the verdict in the
+//! [answer database](crate::answers).
+//!
+//! Answers the database already knows to be wrong are refused without a
+//! request, as are answers for parts that already have an accepted answer.
+
+use std::{fmt::Display, time::Duration};
+
+#[cfg(feature = "remote")]
+use std::path::Path;
+
+#[cfg(feature = "remote")]
+use anyhow::{bail, Context};
+
+#[cfg(feature = "remote")]
+use crate::{
+    answers::{AnswerDb, Relation, Verdict},
+    remote::Client,
+    solution::{Answer, Part},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// An answer was submitted too recently, `wait` is how long the site asks
+    /// to wait if it said so.
+    RateLimited {
+        wait: Option<Duration>,
+    },
+    /// The part was already solved on the site.
+    AlreadySolved,
+    /// The response did not match any known message, its text is kept as is.
+    Unrecognized(String),
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited { wait: Some(wait) } => {
+                write!(f, "rate limited, wait {wait:?}")
+            }
+            Outcome::RateLimited { wait: None } => write!(f, "rate limited"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+            Outcome::Unrecognized(text) => write!(f, "unrecognized response: {text}"),
+        }
+    }
+}
+
+impl Outcome {
+    /// How the answer is recorded in the database, rate limited and
+    /// unrecognized responses say nothing about it.
+    #[cfg(feature = "remote")]
+    fn relation(&self) -> Option<Relation> {
+        match self {
+            Outcome::Correct => Some(Relation::Correct),
+            Outcome::TooHigh => Some(Relation::TooHigh),
+            Outcome::TooLow => Some(Relation::TooLow),
+            Outcome::Wrong => Some(Relation::Wrong),
+            Outcome::RateLimited { .. } | Outcome::AlreadySolved | Outcome::Unrecognized(_) => None,
+        }
+    }
+}
+
+/// Reads the outcome from the HTML page the site answers a submission with.
+pub fn parse_response(html: &str) -> Outcome {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("too high") {
+        Outcome::TooHigh
+    } else if text.contains("too low") {
+        Outcome::TooLow
+    } else if text.contains("That's not the right answer") {
+        Outcome::Wrong
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::RateLimited {
+            wait: parse_wait(&text),
+        }
+    } else if text.contains("Did you already complete it") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unrecognized(text)
+    }
+}
+
+/// The text of the `<article>` holding the message, without tags.
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Reads the wait out of "You have 1m 20s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, rest) = text.split_once("You have ")?;
+    let (wait, _) = rest.split_once(" left to wait")?;
+    wait.split_whitespace()
+        .map(|amount| {
+            let unit = amount.trim_start_matches(|c: char| c.is_ascii_digit());
+            let number: u64 = amount[..amount.len() - unit.len()].parse().ok()?;
+            match unit {
+                "h" => Some(number * 60 * 60),
+                "m" => Some(number * 60),
+                "s" => Some(number),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Submits `answer` to the server of `client` unless `db`, the database of
+/// `year` loaded from `db_path`, already has a verdict for it. The outcome is
+/// recorded in `db` and appended to `db_path` before it is returned.
+#[cfg(feature = "remote")]
+pub fn submit(
+    client: &Client,
+    db: &mut AnswerDb,
+    db_path: &Path,
+    year: u16,
+    day: u8,
+    part: Part,
+    answer: &Answer,
+) -> anyhow::Result<Outcome> {
+    match db.check(day, part, answer) {
+        Verdict::Correct => return Ok(Outcome::AlreadySolved),
+        verdict @ (Verdict::Wrong { .. } | Verdict::KnownWrong) => {
+            bail!("Not submitting {answer} for {year} day {day} {part}, it is {verdict}")
+        }
+        Verdict::Unknown => {}
+    }
+    let level = part.number().to_string();
+    let response = client.post_form(
+        &client.day_url(year, day, "/answer"),
+        &[("level", &level), ("answer", &answer.0)],
+    )?;
+    let outcome = parse_response(&response);
+    if let Some(relation) = outcome.relation() {
+        // the site took the answer, so a lost verdict could send it again
+        db.record(db_path, day, part, relation, answer)
+            .with_context(|| format!("{outcome}, but the verdict was not recorded"))?;
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "remote")]
+    use std::path::PathBuf;
+
+    use super::*;
+    #[cfg(feature = "remote")]
+    use crate::{remote::stub::Stub, temp_dir};
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn responses() {
+        let cases = [
+            (
+                "That's the right answer!  You are <em>one gold star</em> closer.",
+                Outcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Outcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Outcome::TooLow,
+            ),
+            (
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data.",
+                Outcome::Wrong,
+            ),
+            (
+                "You gave an answer too recently.  You have 1m 20s left to wait.",
+                Outcome::RateLimited {
+                    wait: Some(Duration::from_secs(80)),
+                },
+            ),
+            (
+                "You gave an answer too recently.",
+                Outcome::RateLimited { wait: None },
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Outcome::AlreadySolved,
+            ),
+        ];
+        for (message, outcome) in cases {
+            assert_eq!(parse_response(&page(message)), outcome, "{message}");
+        }
+    }
+
+    #[test]
+    fn unrecognized_response_keeps_the_text() {
+        let outcome = parse_response(&page("Something <b>new</b>\n  happened."));
+        assert_eq!(
+            outcome,
+            Outcome::Unrecognized("Something new happened.".to_string())
+        );
+        let outcome = parse_response("no article at all");
+        assert_eq!(
+            outcome,
+            Outcome::Unrecognized("no article at all".to_string())
+        );
+    }
+
+    #[test]
+    fn waits() {
+        let wait = |text| parse_wait(text).map(|wait| wait.as_secs());
+        assert_eq!(wait("You have 45s left to wait."), Some(45));
+        assert_eq!(wait("You have 4m 5s left to wait."), Some(245));
+        assert_eq!(wait("You have 1h 0m 1s left to wait."), Some(3601));
+        assert_eq!(wait("You have a while left to wait."), None);
+        assert_eq!(wait("You have 5 left to wait."), None);
+        assert_eq!(wait("Please wait."), None);
+    }
+
+    /// Submits `answer` for day 1 part 1 to a stub answering with `message`,
+    /// with an answer database at a path whose directory does not exist yet.
+    #[cfg(feature = "remote")]
+    fn submit_to_stub(
+        name: &str,
+        known: &str,
+        answer: &str,
+        message: &'static str,
+    ) -> (anyhow::Result<Outcome>, Stub, PathBuf) {
+        let stub = Stub::start(move |_| (200, page(message)));
+        let dir = temp_dir(name);
+        let client = Client::new(&stub.url, "secret")
+            .with_throttle(Duration::ZERO)
+            .with_state_dir(&dir);
+        let db_path = dir.join("2024").join("answers.txt");
+        let mut db = AnswerDb::parse(known).unwrap();
+        let outcome = submit(
+            &client,
+            &mut db,
+            &db_path,
+            2024,
+            1,
+            Part::One,
+            &Answer(answer.to_string()),
+        );
+        (outcome, stub, db_path)
+    }
+
+    #[test]
+    #[cfg(feature = "remote")]
+    fn correct_answer_is_recorded() {
+        let (outcome, stub, db_path) =
+            submit_to_stub("submit-correct", "", "11", "That's the right answer!");
+        assert_eq!(outcome.unwrap(), Outcome::Correct);
+        let seen = stub.seen();
+        assert_eq!(seen.len(), 1);
+        assert!(
+            seen[0].url.ends_with("/2024/day/1/answer"),
+            "{}",
+            seen[0].url
+        );
+        let recorded = std::fs::read_to_string(db_path).unwrap();
+        assert_eq!(recorded, "day-1 part_1 = 11\n");
+    }
+
+    #[test]
+    #[cfg(feature = "remote")]
+    fn known_wrong_answer_sends_no_request() {
+        let (outcome, stub, db_path) = submit_to_stub(
+            "submit-known-wrong",
+            "day-1 part_1 < 20\n",
+            "25",
+            "That's the right answer!",
+        );
+        assert!(outcome.is_err());
+        assert!(stub.seen().is_empty());
+        assert!(!db_path.exists());
+    }
+
+    #[test]
+    #[cfg(feature = "remote")]
+    fn rate_limited_answer_is_not_recorded() {
+        let (outcome, stub, db_path) = submit_to_stub(
+            "submit-rate-limited",
+            "",
+            "11",
+            "You gave an answer too recently.  You have 30s left to wait.",
+        );
+        let wait = Some(Duration::from_secs(30));
+        assert_eq!(outcome.unwrap(), Outcome::RateLimited { wait });
+        assert_eq!(stub.seen().len(), 1);
+        assert!(!db_path.exists());
+    }
+}
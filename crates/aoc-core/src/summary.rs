@@ -0,0 +1,179 @@
+//! The summary table printed after `aoc run`.
+
+use std::{io::Write, time::Duration};
+
+use crate::{
+    answers::{AnswerDb, Verdict},
+    runner::DayReport,
+};
+
+struct Row {
+    day: String,
+    part: String,
+    answer: String,
+    check: String,
+    time: String,
+}
+
+/// Writes a table with the answer, its [`Verdict`] and the time of every part,
+/// followed by the total time. `wall_time` is the time the whole run took,
+/// which is less than the total when days ran in parallel.
+pub fn write_summary(
+    reports: &[DayReport],
+    db: &AnswerDb,
+    wall_time: Duration,
+    mut w: impl Write,
+) -> anyhow::Result<()> {
+    let mut rows = vec![];
+    let mut total = Duration::ZERO;
+    let mut correct = 0;
+    let mut parts = 0;
+    for report in reports {
+        let parse = match &report.parse {
+            Ok(parse) => *parse,
+            Err(e) => {
+                parts += 2;
+                rows.push(Row {
+                    day: report.day.to_string(),
+                    part: "-".to_string(),
+                    answer: String::new(),
+                    // validation errors list one problem per line
+                    check: format!(
+                        "failed: {}",
+                        e.lines().map(str::trim).collect::<Vec<_>>().join("; ")
+                    ),
+                    time: String::new(),
+                });
+                continue;
+            }
+        };
+        total += parse;
+        for part in &report.parts {
+            parts += 1;
+            total += part.elapsed;
+            let (answer, check) = match part.answer() {
+                Some(answer) => {
+                    let verdict = db.check(report.day, part.part, answer);
+                    correct += (verdict == Verdict::Correct) as usize;
+                    (answer.to_string(), verdict.to_string())
+                }
+                None => (String::new(), part.status.to_string()),
+            };
+            rows.push(Row {
+                day: report.day.to_string(),
+                part: part.part.number().to_string(),
+                answer,
+                check,
+                time: format!("{:.2?}", part.elapsed),
+            });
+        }
+    }
+
+    let header = Row {
+        day: "Day".to_string(),
+        part: "Part".to_string(),
+        answer: "Answer".to_string(),
+        check: "Check".to_string(),
+        time: "Time".to_string(),
+    };
+    let width = |column: fn(&Row) -> &String| {
+        std::iter::once(&header)
+            .chain(&rows)
+            .map(|row| column(row).chars().count())
+            .max()
+            .unwrap_or(0)
+    };
+    let widths = [
+        width(|row| &row.day),
+        width(|row| &row.part),
+        width(|row| &row.answer),
+        width(|row| &row.check),
+        width(|row| &row.time),
+    ];
+    let write_row = |w: &mut dyn Write, row: &Row| {
+        let line = format!(
+            "{:>w0$}  {:>w1$}  {:>w2$}  {:<w3$}  {:>w4$}",
+            row.day,
+            row.part,
+            row.answer,
+            row.check,
+            row.time,
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+            w4 = widths[4],
+        );
+        writeln!(w, "{}", line.trim_end())
+    };
+    write_row(&mut w, &header)?;
+    writeln!(w, "{}", "-".repeat(widths.iter().sum::<usize>() + 2 * 4))?;
+    for row in &rows {
+        write_row(&mut w, row)?;
+    }
+    writeln!(w)?;
+    writeln!(w, "{correct}/{parts} parts correct")?;
+    writeln!(
+        w,
+        "total {:.2?} (wall clock {:.2?}), parsing included",
+        total, wall_time
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        runner::{PartReport, Status},
+        solution::{Answer, Part},
+    };
+
+    fn part(part: Part, status: Status, millis: u64) -> PartReport {
+        PartReport {
+            year: 2024,
+            day: 1,
+            part,
+            status,
+            elapsed: Duration::from_millis(millis),
+            alloc: None,
+        }
+    }
+
+    #[test]
+    fn summary() {
+        let reports = [
+            DayReport {
+                year: 2024,
+                day: 1,
+                input_hash: None,
+                parse: Ok(Duration::from_millis(1)),
+                parts: vec![
+                    part(Part::One, Status::Solved(Answer("11".to_string())), 2),
+                    part(Part::Two, Status::Solved(Answer("30".to_string())), 3),
+                ],
+            },
+            DayReport {
+                year: 2024,
+                day: 10,
+                input_hash: None,
+                parse: Err("Invalid input, 2 problem(s)\n  1:1: a\n  2:1: b".to_string()),
+                parts: vec![],
+            },
+        ];
+        let db = AnswerDb::parse("day-1 part_1 = 11\nday-1 part_2 > 30\n").unwrap();
+        let mut out = vec![];
+        write_summary(&reports, &db, Duration::from_millis(4), &mut out).unwrap();
+        let expected = "\
+Day  Part  Answer  Check                                                  Time
+------------------------------------------------------------------------------
+  1     1      11  correct                                              2.00ms
+  1     2      30  known wrong                                          3.00ms
+ 10     -          failed: Invalid input, 2 problem(s); 1:1: a; 2:1: b
+
+1/4 parts correct
+total 6.00ms (wall clock 4.00ms), parsing included
+";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+}
@@ -0,0 +1,128 @@
+//! Checks that an input has the shape a solver relies on, before parsing it.
+//!
+//! Each day lists every problem it finds through
+//! [`Solution::validate`](crate::Solution::validate), so a broken input is
+//! reported in one go instead of one panic at a time.
+
+use std::fmt::Display;
+
+use thiserror::Error;
+
+/// A problem at a 1-based line and column of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct Invalid(pub Vec<Diagnostic>);
+
+impl Display for Invalid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid input, {} problem(s)", self.0.len())?;
+        for diagnostic in &self.0 {
+            write!(f, "\n  {diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Invalid {
+    /// Fails with the `diagnostics`, sorted by location, unless there are none.
+    pub fn check(mut diagnostics: Vec<Diagnostic>) -> Result<(), Invalid> {
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+        match diagnostics.is_empty() {
+            true => Ok(()),
+            false => Err(Invalid(diagnostics)),
+        }
+    }
+}
+
+/// The lines of `input`, numbered from 1.
+pub fn lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().enumerate().map(|(i, line)| (i + 1, line))
+}
+
+/// The 1-based column where `part`, a slice of `line`, starts.
+pub fn column(line: &str, part: &str) -> usize {
+    let offset = part.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count() + 1
+}
+
+/// Checks that `input` is a non-empty rectangle of characters accepted by
+/// `allowed`.
+pub fn grid(input: &str, allowed: impl Fn(char) -> bool) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let Some(width) = input.lines().next().map(|line| line.chars().count()) else {
+        return vec![Diagnostic::new(1, 1, "empty input")];
+    };
+    for (y, line) in lines(input) {
+        let len = line.chars().count();
+        if len != width {
+            diagnostics.push(Diagnostic::new(
+                y,
+                len.min(width) + 1,
+                format!("expected {width} columns, found {len}"),
+            ));
+        }
+        for (x, c) in line.chars().enumerate() {
+            if !allowed(c) {
+                diagnostics.push(Diagnostic::new(y, x + 1, format!("unexpected {c:?}")));
+            }
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn columns_count_chars() {
+        let line = "é 12  x";
+        let part = &line[line.find('x').unwrap()..];
+        assert_eq!(column(line, part), 7);
+        assert_eq!(column(line, line), 1);
+    }
+
+    #[test]
+    fn grid_locations() {
+        let found = grid("ab\nabc\na#\n", |c| c.is_ascii_lowercase());
+        let found: Vec<String> = found.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            found,
+            ["2:3: expected 2 columns, found 3", "3:2: unexpected '#'"]
+        );
+        assert_eq!(grid("", |_| true), [Diagnostic::new(1, 1, "empty input")]);
+    }
+
+    #[test]
+    fn check_sorts_by_location() {
+        let invalid = Invalid::check(vec![Diagnostic::new(2, 1, "b"), Diagnostic::new(1, 4, "a")])
+            .unwrap_err();
+        assert_eq!(
+            invalid.to_string(),
+            "Invalid input, 2 problem(s)\n  1:4: a\n  2:1: b"
+        );
+        assert_eq!(Invalid::check(vec![]), Ok(()));
+    }
+}
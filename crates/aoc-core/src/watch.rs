@@ -0,0 +1,299 @@
+//! Re-runs a day whenever its input or the code changes.
+//!
+//! The input directory of the year is polled for `day-N.txt` and any
+//! `day-N-*.txt` side inputs. After every run the answers are compared with
+//! the previous run and with the [answer database](crate::answers).
+//!
+//! The `.rs` files under the `src` directories of the workspace are polled
+//! too. When one changes the binary is rebuilt with `cargo build` and started
+//! again with the same arguments, a failed build keeps the old one running.
+//! The last run is handed to the new process in [`PREVIOUS_VAR`], so the
+//! first run after a restart is still compared with it.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    alloc,
+    answers::{AnswerDb, Verdict},
+    input,
+    runner::{self, DayReport, PartReport, RunOptions},
+    solution::Solver,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Environment variable holding the last run, as JSON, across a restart.
+pub const PREVIOUS_VAR: &str = "AOC_WATCH_PREVIOUS";
+
+/// What a run is compared with: the status and time of each part of the run
+/// before it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Previous {
+    year: u16,
+    day: u8,
+    parts: Vec<PreviousPart>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PreviousPart {
+    part: u8,
+    /// The [`Status`](runner::Status) as it is displayed.
+    status: String,
+    elapsed: Duration,
+}
+
+impl Previous {
+    fn new(report: &DayReport) -> Self {
+        let parts = report
+            .parts
+            .iter()
+            .map(|part| PreviousPart {
+                part: part.part.number(),
+                status: part.status.to_string(),
+                elapsed: part.elapsed,
+            })
+            .collect();
+        Previous {
+            year: report.year,
+            day: report.day,
+            parts,
+        }
+    }
+
+    fn part(&self, report: &PartReport) -> Option<&PreviousPart> {
+        self.parts
+            .iter()
+            .find(|before| before.part == report.part.number())
+    }
+
+    /// The run handed over by the process before a restart, if it was of the
+    /// same day.
+    fn inherited(year: u16, day: u8) -> Option<Self> {
+        let json = std::env::var(PREVIOUS_VAR).ok()?;
+        let previous: Previous = serde_json::from_str(&json)
+            .inspect_err(|e| tracing::warn!(year, day, "ignoring {PREVIOUS_VAR}: {e}"))
+            .ok()?;
+        (previous.year == year && previous.day == day).then_some(previous)
+    }
+}
+
+/// Modification times of the watched files, missing files are left out.
+fn snapshot(year: u16, day: u8) -> BTreeMap<PathBuf, SystemTime> {
+    let prefix = format!("day-{day}-");
+    let main = input::path(year, day);
+    let side_inputs = std::fs::read_dir(input::year_dir(year))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+        });
+    std::iter::once(main)
+        .chain(side_inputs)
+        .flat_map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|meta| meta.modified());
+            modified.ok().map(|modified| (path, modified))
+        })
+        .collect()
+}
+
+/// The root of the workspace, whose `src` directories hold the code.
+fn workspace() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// Modification times of the `.rs` files under `dir`.
+fn sources(dir: &Path, times: &mut BTreeMap<PathBuf, SystemTime>) {
+    for path in std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+    {
+        if path.is_dir() {
+            sources(&path, times);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            if let Ok(modified) = std::fs::metadata(&path).and_then(|meta| meta.modified()) {
+                times.insert(path, modified);
+            }
+        }
+    }
+}
+
+/// Modification times of the code of the binary and the crates it links.
+fn code_snapshot() -> BTreeMap<PathBuf, SystemTime> {
+    let root = workspace();
+    let mut times = BTreeMap::new();
+    sources(&root.join("src"), &mut times);
+    for krate in std::fs::read_dir(root.join("crates"))
+        .into_iter()
+        .flatten()
+        .flatten()
+    {
+        sources(&krate.path().join("src"), &mut times);
+    }
+    times
+}
+
+/// Rebuilds the binary at `exe` with the profile and features the running one
+/// was built with, returning whether the build succeeded.
+fn rebuild(exe: &Path) -> anyhow::Result<bool> {
+    let name = exe
+        .file_stem()
+        .and_then(|name| name.to_str())
+        .unwrap_or("aoc");
+    let mut cargo = Command::new(std::env::var_os("CARGO").unwrap_or("cargo".into()));
+    cargo
+        .current_dir(workspace())
+        .args(["build", "--bin", name]);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    if alloc::enabled() {
+        cargo.args(["--features", "count-allocs"]);
+    }
+    Ok(cargo.status()?.success())
+}
+
+/// Replaces the process with a fresh start of the binary at `exe`, with the
+/// same arguments and `previous` in [`PREVIOUS_VAR`].
+fn restart(exe: &Path, previous: Option<&Previous>) -> anyhow::Result<()> {
+    let mut command = Command::new(exe);
+    command.args(std::env::args_os().skip(1));
+    match previous {
+        Some(previous) => command.env(PREVIOUS_VAR, serde_json::to_string(previous)?),
+        None => command.env_remove(PREVIOUS_VAR),
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        Err(command.exec().into())
+    }
+    #[cfg(not(unix))]
+    std::process::exit(command.status()?.code().unwrap_or(1))
+}
+
+/// Watches the inputs and the code of `solver` and re-runs it on every change,
+/// restarting on a successful rebuild. Never returns unless the answer
+/// database cannot be read or the binary cannot be restarted.
+pub fn watch(solver: &'static dyn Solver, options: &RunOptions) -> anyhow::Result<()> {
+    let (year, day) = (solver.year(), solver.day());
+    tracing::info!(year, day, "watching {}", input::path(year, day).display());
+    let mut seen = None;
+    // resolved up front, once rebuilt the running binary no longer has a path
+    let exe = std::env::current_exe()?;
+    let mut code = code_snapshot();
+    let mut previous = Previous::inherited(year, day);
+    loop {
+        let current_code = code_snapshot();
+        if current_code != code {
+            code = current_code;
+            tracing::info!(year, day, "code changed, rebuilding");
+            match rebuild(&exe)? {
+                true => restart(&exe, previous.as_ref())?,
+                false => tracing::warn!(year, day, "build failed, keeping the old binary"),
+            }
+        }
+        let current = snapshot(year, day);
+        if seen.as_ref() != Some(&current) {
+            seen = Some(current);
+            let db = AnswerDb::load(year)?;
+            let report = runner::run_day(solver, options);
+            for part in &report.parts {
+                let before = previous.as_ref().and_then(|previous| previous.part(part));
+                log_changes(part, before, &db);
+            }
+            previous = Some(Previous::new(&report));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The change in run time from `before` to `now`, there is none to show when
+/// the earlier run took no measurable time.
+fn delta(now: Duration, before: Duration) -> Option<String> {
+    if before.is_zero() {
+        return None;
+    }
+    let ratio = now.as_secs_f64() / before.as_secs_f64();
+    Some(format!("{:+.1}%", (ratio - 1.0) * 100.0))
+}
+
+fn log_changes(report: &PartReport, before: Option<&PreviousPart>, db: &AnswerDb) {
+    let day = report.day;
+    let part = report.part.to_string();
+    let status = report.status.to_string();
+    let delta = before.and_then(|before| delta(report.elapsed, before.elapsed));
+    let delta = delta.as_deref().unwrap_or("-");
+    if let Some(before) = before.filter(|before| before.status != status) {
+        tracing::warn!(day, part, "changed from {} to {}", before.status, status);
+    }
+    let Some(answer) = report.answer() else {
+        tracing::info!(day, part, status = %report.status, delta);
+        return;
+    };
+    match db.check(day, report.part, answer) {
+        verdict @ (Verdict::Correct | Verdict::Unknown) => {
+            tracing::info!(day, part, %answer, %verdict, delta)
+        }
+        verdict => tracing::warn!(day, part, %answer, %verdict, delta),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{runner::Status, solution::Part};
+
+    fn part(part: Part, status: Status, millis: u64) -> PartReport {
+        PartReport {
+            year: 2024,
+            day: 1,
+            part,
+            status,
+            elapsed: Duration::from_millis(millis),
+            alloc: None,
+        }
+    }
+
+    #[test]
+    fn delta_needs_a_measured_run() {
+        let ms = Duration::from_millis;
+        assert_eq!(delta(ms(15), ms(10)).as_deref(), Some("+50.0%"));
+        assert_eq!(delta(ms(5), ms(10)).as_deref(), Some("-50.0%"));
+        assert_eq!(delta(ms(5), Duration::ZERO), None);
+    }
+
+    #[test]
+    fn previous_survives_json() {
+        let report = DayReport {
+            year: 2024,
+            day: 1,
+            input_hash: None,
+            parse: Ok(Duration::ZERO),
+            parts: vec![
+                part(Part::One, Status::Solved(11u64.into()), 3),
+                part(Part::Two, Status::Failed("no".into()), 4),
+            ],
+        };
+        let previous = Previous::new(&report);
+        let json = serde_json::to_string(&previous).unwrap();
+        let back: Previous = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, previous);
+
+        let two = back.part(&report.parts[1]).unwrap();
+        assert_eq!(two.status, "failed: no");
+        assert_eq!(two.elapsed, Duration::from_millis(4));
+    }
+}
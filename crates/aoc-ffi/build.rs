@@ -0,0 +1,24 @@
+//! Generates the header into `$OUT_DIR/aoc.h` from the exported functions.
+//!
+//! The source tree is only written on request: `AOC_FFI_HEADER=include/aoc.h`
+//! also writes the header there, relative to this crate. A test checks that the
+//! checked-in copy is up to date.
+
+fn main() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=AOC_FFI_HEADER");
+    let config = cbindgen::Config::from_file(format!("{crate_dir}/cbindgen.toml"))
+        .expect("invalid cbindgen.toml");
+    let header = cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("could not generate the header");
+    header.write_to_file(format!("{out_dir}/aoc.h"));
+    if let Some(path) = std::env::var_os("AOC_FFI_HEADER") {
+        header.write_to_file(std::path::Path::new(&crate_dir).join(path));
+    }
+}
@@ -0,0 +1,74 @@
+#ifndef AOC_FFI_H
+#define AOC_FFI_H
+
+/* Generated by cbindgen from crates/aoc-ffi/src/lib.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Result of a call, `AOC_STATUS_OK` on success.
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  /**
+   * The day is not solved.
+   */
+  AOC_STATUS_UNKNOWN_DAY = 1,
+  /**
+   * The part is not 1 or 2.
+   */
+  AOC_STATUS_UNKNOWN_PART = 2,
+  /**
+   * A pointer is null, or the input is not UTF-8.
+   */
+  AOC_STATUS_INVALID_ARGUMENT = 3,
+  /**
+   * The input does not parse.
+   */
+  AOC_STATUS_PARSE_FAILED = 4,
+  /**
+   * The part returned an error.
+   */
+  AOC_STATUS_SOLVE_FAILED = 5,
+  /**
+   * The answer does not fit, `*out_len` is set to the length it needs.
+   */
+  AOC_STATUS_BUFFER_TOO_SMALL = 6,
+  /**
+   * The solver panicked.
+   */
+  AOC_STATUS_PANICKED = 7,
+} AocStatus;
+
+/**
+ * Solves one part of a day of the latest year.
+ *
+ * `input_ptr` points to `input_len` bytes of UTF-8 input. `out_buf` points to
+ * `*out_len` writable bytes. On success the answer is written to `out_buf`,
+ * followed by a NUL, and `*out_len` is set to its length without the NUL.
+ * When the call fails with a message, the message is written the same way,
+ * cut short to fit.
+ *
+ * # Safety
+ *
+ * The pointers must be valid for the given lengths, and the buffers must not
+ * be used by anything else during the call.
+ */
+enum AocStatus aoc_solve(uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input_ptr,
+                         size_t input_len,
+                         char *out_buf,
+                         size_t *out_len);
+
+/**
+ * A static, NUL terminated description of a status returned by [`aoc_solve`].
+ * Takes an `int` so that any value coming from C is safe to pass.
+ */
+const char *aoc_status_message(int status);
+
+#endif  /* AOC_FFI_H */
@@ -0,0 +1,281 @@
+//! C ABI for the solvers, built as a shared library. The header is generated
+//! into `$OUT_DIR/aoc.h` by the build script, `include/aoc.h` is a checked-in
+//! copy.
+//!
+//! Nothing here unwinds into the caller: panics in a solver are caught and
+//! reported as [`AocStatus::Panicked`].
+
+use std::{
+    ffi::{c_char, c_int, CStr},
+    panic::{self, AssertUnwindSafe},
+};
+
+use aoc2024::{cancel::Cancel, days, Part, Registry};
+
+/// Result of a call, `AOC_STATUS_OK` on success.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    /// The day is not solved.
+    UnknownDay = 1,
+    /// The part is not 1 or 2.
+    UnknownPart = 2,
+    /// A pointer is null, or the input is not UTF-8.
+    InvalidArgument = 3,
+    /// The input does not parse.
+    ParseFailed = 4,
+    /// The part returned an error.
+    SolveFailed = 5,
+    /// The answer does not fit, `*out_len` is set to the length it needs.
+    BufferTooSmall = 6,
+    /// The solver panicked.
+    Panicked = 7,
+}
+
+/// Solves one part of a day of the latest year.
+///
+/// `input_ptr` points to `input_len` bytes of UTF-8 input. `out_buf` points to
+/// `*out_len` writable bytes. On success the answer is written to `out_buf`,
+/// followed by a NUL, and `*out_len` is set to its length without the NUL.
+/// When the call fails with a message, the message is written the same way,
+/// cut short to fit.
+///
+/// # Safety
+///
+/// The pointers must be valid for the given lengths, and the buffers must not
+/// be used by anything else during the call.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut c_char,
+    out_len: *mut usize,
+) -> AocStatus {
+    if input_ptr.is_null() || out_buf.is_null() || out_len.is_null() {
+        return AocStatus::InvalidArgument;
+    }
+    let input = std::slice::from_raw_parts(input_ptr, input_len);
+    let out = std::slice::from_raw_parts_mut(out_buf.cast::<u8>(), *out_len);
+    let (status, text) = match panic::catch_unwind(AssertUnwindSafe(|| solve(day, part, input))) {
+        Ok(Ok(answer)) => (AocStatus::Ok, answer),
+        Ok(Err((status, message))) => (status, message),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("no message");
+            (AocStatus::Panicked, format!("Solver panicked: {message}"))
+        }
+    };
+    match (write(out, &text), status) {
+        (Ok(len), status) => {
+            *out_len = len;
+            status
+        }
+        (Err(needed), AocStatus::Ok) => {
+            *out_len = needed;
+            AocStatus::BufferTooSmall
+        }
+        // the status says what went wrong even when the message is cut short
+        (Err(_), status) => {
+            *out_len = truncate(out, &text);
+            status
+        }
+    }
+}
+
+/// A static, NUL terminated description of a status returned by [`aoc_solve`].
+/// Takes an `int` so that any value coming from C is safe to pass.
+#[no_mangle]
+pub extern "C" fn aoc_status_message(status: c_int) -> *const c_char {
+    let message: &'static CStr = match status {
+        0 => c"ok",
+        1 => c"day is not solved",
+        2 => c"part must be 1 or 2",
+        3 => c"null pointer or input is not UTF-8",
+        4 => c"input does not parse",
+        5 => c"solver failed",
+        6 => c"output buffer is too small",
+        7 => c"solver panicked",
+        _ => c"unknown status",
+    };
+    message.as_ptr()
+}
+
+#[cfg(not(test))]
+fn registry() -> Registry {
+    days::registry()
+}
+#[cfg(test)]
+use tests::registry;
+
+fn solve(day: u8, part: u8, input: &[u8]) -> Result<String, (AocStatus, String)> {
+    let registry = registry();
+    let solver = registry
+        .get(days::YEAR, day)
+        .ok_or((AocStatus::UnknownDay, format!("Day {day} is not solved")))?;
+    let part = Part::from_number(part).ok_or((
+        AocStatus::UnknownPart,
+        format!("Unknown part {part}, expected 1 or 2"),
+    ))?;
+    let input = std::str::from_utf8(input).map_err(|e| {
+        (
+            AocStatus::InvalidArgument,
+            format!("Input is not UTF-8: {e}"),
+        )
+    })?;
+    let parsed = solver
+        .parse(input)
+        .map_err(|e| (AocStatus::ParseFailed, format!("{e:#}")))?;
+    let answer = solver
+        .solve(&parsed, part, &Cancel::new())
+        .map_err(|e| (AocStatus::SolveFailed, format!("{e:#}")))?;
+    Ok(answer.to_string())
+}
+
+/// Writes `text` and a NUL to `out`, or returns the length `out` would need
+/// without the NUL.
+fn write(out: &mut [u8], text: &str) -> Result<usize, usize> {
+    if text.len() >= out.len() {
+        return Err(text.len());
+    }
+    out[..text.len()].copy_from_slice(text.as_bytes());
+    out[text.len()] = 0;
+    Ok(text.len())
+}
+
+/// Writes as much of `text` as fits in `out` with a NUL, on a char boundary.
+fn truncate(out: &mut [u8], text: &str) -> usize {
+    let Some(room) = out.len().checked_sub(1) else {
+        return 0;
+    };
+    let len = (0..=room.min(text.len()))
+        .rev()
+        .find(|&len| text.is_char_boundary(len))
+        .unwrap_or(0);
+    out[..len].copy_from_slice(&text.as_bytes()[..len]);
+    out[len] = 0;
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc2024::{solution::Answer, Solution};
+
+    use super::*;
+
+    /// Day 25 panics in part 1 and fails in part 2.
+    struct Broken;
+
+    impl Solution for Broken {
+        const YEAR: u16 = days::YEAR;
+        const DAY: u8 = 25;
+        type Input = ();
+
+        fn parse(_: &str) -> anyhow::Result<Self::Input> {
+            Ok(())
+        }
+
+        fn part_1(_: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+            panic!("out of stars");
+            #[allow(unreachable_code)]
+            Ok(0)
+        }
+
+        fn part_2(_: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+            Err::<u64, _>(anyhow::anyhow!("{}", "é".repeat(8)))
+        }
+    }
+
+    pub(super) fn registry() -> Registry {
+        days::registry().with(&[&Broken])
+    }
+
+    /// Calls [`aoc_solve`] with an output buffer of `size` bytes.
+    fn call(day: u8, part: u8, input: &str, size: usize) -> (AocStatus, usize, Vec<u8>) {
+        let mut out = vec![0xff_u8; size];
+        let mut len = size;
+        let status = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr().cast(),
+                &mut len,
+            )
+        };
+        (status, len, out)
+    }
+
+    fn text(out: &[u8]) -> &str {
+        CStr::from_bytes_until_nul(out).unwrap().to_str().unwrap()
+    }
+
+    #[test]
+    fn answer() {
+        let (status, len, out) = call(1, 1, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n", 8);
+        assert_eq!((status, len, text(&out)), (AocStatus::Ok, 2, "11"));
+    }
+
+    #[test]
+    fn null_pointers() {
+        let mut out = [0u8; 8];
+        let mut len = out.len();
+        let status =
+            unsafe { aoc_solve(1, 1, std::ptr::null(), 0, out.as_mut_ptr().cast(), &mut len) };
+        assert_eq!(status, AocStatus::InvalidArgument);
+        let status = unsafe { aoc_solve(1, 1, b"".as_ptr(), 0, std::ptr::null_mut(), &mut len) };
+        assert_eq!(status, AocStatus::InvalidArgument);
+        let status = unsafe {
+            aoc_solve(
+                1,
+                1,
+                b"".as_ptr(),
+                0,
+                out.as_mut_ptr().cast(),
+                std::ptr::null_mut(),
+            )
+        };
+        assert_eq!(status, AocStatus::InvalidArgument);
+    }
+
+    #[test]
+    fn buffer_too_small() {
+        // the answer needs 2 bytes and the NUL
+        let (status, len, out) = call(1, 1, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n", 2);
+        assert_eq!((status, len), (AocStatus::BufferTooSmall, 2));
+        assert_eq!(out, [0xff; 2]);
+    }
+
+    #[test]
+    fn messages_are_truncated_on_char_boundaries() {
+        let (status, len, out) = call(25, 2, "", 6);
+        assert_eq!((status, len, text(&out)), (AocStatus::SolveFailed, 4, "éé"));
+        let (status, len, _) = call(25, 2, "", 0);
+        assert_eq!((status, len), (AocStatus::SolveFailed, 0));
+        let (status, _, out) = call(0, 1, "", 8);
+        assert_eq!((status, text(&out)), (AocStatus::UnknownDay, "Day 0 i"));
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        let (status, len, out) = call(25, 1, "", 64);
+        assert_eq!(status, AocStatus::Panicked);
+        assert_eq!(text(&out), "Solver panicked: out of stars");
+        assert_eq!(len, text(&out).len());
+    }
+
+    #[test]
+    fn checked_in_header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc.h"));
+        assert!(
+            generated == include_str!("../include/aoc.h"),
+            "regenerate it with `AOC_FFI_HEADER=include/aoc.h cargo build -p aoc-ffi`"
+        );
+    }
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+//! Day 5's validation and parsing. Going through [`Solver::parse`] keeps
+//! input the validation rejects away from `Day5::parse`, so a crash is a bug.
+
+use aoc2024::{days::day_5::Day5, Solver};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Solver::parse(&Day5, input);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+//! Day 8's validation and parsing. Going through [`Solver::parse`] keeps
+//! input the validation rejects away from `Day8::parse`, so a crash is a bug.
+
+use aoc2024::{days::day_8::Day8, Solver};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Solver::parse(&Day8, input);
+});
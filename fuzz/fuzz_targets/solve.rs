@@ -0,0 +1,21 @@
+#![no_main]
+
+//! The whole pipeline of a day, the first byte picks it. `solver.parse` runs
+//! the validation before parsing, so the parts only see input it lets through
+//! and a crash anywhere is a bug.
+
+use aoc2024::{cancel::Cancel, days::SOLUTIONS, Part};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: (u8, &str)| {
+    let (day, input) = data;
+    let Some(solver) = SOLUTIONS.iter().find(|solver| solver.day() == day) else {
+        return;
+    };
+    let Ok(parsed) = solver.parse(input) else {
+        return;
+    };
+    for part in Part::ALL {
+        let _ = solver.solve(&parsed, part, &Cancel::new());
+    }
+});
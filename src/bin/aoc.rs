@@ -0,0 +1,330 @@
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Context};
+use aoc2024::{
+    alloc,
+    answers::{self, AnswerDb},
+    asciicast::{self, CastOptions},
+    days, explore, generate,
+    image::{self, Image, ImageFormat},
+    input, inspect,
+    leaderboard::{self, Leaderboard},
+    output::{self, Format},
+    remote::Client,
+    runner::{self, RunOptions},
+    scaffold,
+    scene::Scene,
+    serve::{self, Service},
+    submit::{self, Outcome},
+    summary, watch, Answer, Part, Solver,
+};
+use clap::{Parser, Subcommand};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+#[derive(Debug, Parser)]
+#[command(name = "aoc", about = "Advent of Code solutions")]
+struct Cli {
+    /// The year of the days to work on, the latest solved year by default.
+    #[arg(long, global = true)]
+    year: Option<u16>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Solve days using their inputs from `inputs/<year>/day-N.txt`.
+    Run {
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        days: Vec<u8>,
+        /// Run every solved day.
+        #[arg(long)]
+        all: bool,
+        /// Run the days in parallel. Not available with `count-allocs`, whose
+        /// counters cannot tell the days apart.
+        #[arg(long)]
+        parallel: bool,
+        /// Time limit for each part, in seconds.
+        #[arg(long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+        /// Print the results to stdout as a `text` summary, `json` lines, `csv` or a
+        /// `markdown` table.
+        #[arg(long, default_value = "text")]
+        format: Format,
+    },
+    /// Solve a day again every time its input or code changes.
+    Watch {
+        day: u8,
+        /// Time limit for each part, in seconds.
+        #[arg(long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Download the inputs of days that are not in `inputs/` yet.
+    Fetch {
+        #[arg(required = true)]
+        days: Vec<u8>,
+    },
+    /// Submit an answer, solving the part first when no answer is given.
+    Submit {
+        day: u8,
+        #[arg(value_parser = parse_part)]
+        part: Part,
+        answer: Option<String>,
+    },
+    /// Print star timelines and daily rankings from an exported private leaderboard.
+    Leaderboard {
+        file: PathBuf,
+        /// `text` or `markdown`.
+        #[arg(long, default_value = "text")]
+        format: Format,
+    },
+    /// Serve the solvers over HTTP, see `aoc_core::serve` for the routes.
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:3000")]
+        addr: String,
+        /// Time limit for each part, in seconds. Defaults to 60.
+        #[arg(long, value_parser = parse_seconds)]
+        timeout: Option<Duration>,
+    },
+    /// Print statistics about the input of a day.
+    Inspect { day: u8 },
+    /// Step through the states of a grid day's solver in an interactive view.
+    Explore { day: u8 },
+    /// Write the states of a grid day's solver as an asciicast v2 recording, or
+    /// as a sequence of images.
+    Record {
+        day: u8,
+        /// Defaults to `day-N.cast`, or `day-N-frames/` with `--images`.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        /// Write a `ppm` or `png` image per frame into the output directory.
+        #[arg(long)]
+        images: Option<ImageFormat>,
+        /// Pixels per cell side in images.
+        #[arg(long, default_value_t = 4)]
+        scale: usize,
+        /// Frames per second.
+        #[arg(long, default_value_t = 10.0)]
+        fps: f64,
+        /// Keep one step in every N.
+        #[arg(long, default_value_t = 1)]
+        every: usize,
+        /// Keep fewer steps when there would be more frames than this, 1 keeps
+        /// only the last step.
+        #[arg(long, value_parser = parse_frames)]
+        max_frames: Option<usize>,
+    },
+    /// Print a random input for a day.
+    Generate {
+        day: u8,
+        /// Defaults to one picked from the clock, which is logged.
+        #[arg(long)]
+        seed: Option<u64>,
+        /// Lines, records or grid side, depending on the day.
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+    },
+    /// Create the solution and binary of a new day from `templates/day.rs.tmpl`.
+    New { day: u8 },
+}
+
+fn parse_part(value: &str) -> anyhow::Result<Part> {
+    Part::from_number(value.parse()?).context("Expected part 1 or 2")
+}
+
+fn parse_seconds(value: &str) -> anyhow::Result<Duration> {
+    Ok(Duration::try_from_secs_f64(value.parse()?)?)
+}
+
+fn parse_frames(value: &str) -> anyhow::Result<usize> {
+    match value.parse()? {
+        0 => bail!("At least one frame is needed"),
+        frames => Ok(frames),
+    }
+}
+
+/// The recorded states of `solver` on its input.
+fn record(solver: &dyn Solver) -> anyhow::Result<Scene> {
+    let parsed = solver.parse(&input::load(solver.year(), solver.day())?)?;
+    solver
+        .record(&parsed)?
+        .with_context(|| format!("Day {} does not record its states", solver.day()))
+}
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+    let _guard = aoc2024::init_tracing()?;
+    let registry = days::registry();
+    let year = match cli.year {
+        Some(year) => year,
+        None => registry.latest_year().context("No solved days")?,
+    };
+    let solver = |day: u8| {
+        registry
+            .get(year, day)
+            .with_context(|| format!("Day {day} of {year} is not solved"))
+    };
+    match cli.command {
+        Command::Run {
+            days,
+            all,
+            parallel,
+            timeout,
+            format,
+        } => {
+            let solvers = match all {
+                true => registry.year(year),
+                false => days
+                    .iter()
+                    .map(|&day| solver(day))
+                    .collect::<anyhow::Result<Vec<_>>>()?,
+            };
+            if parallel && alloc::enabled() {
+                bail!("--parallel would mix the allocations of the days, build without `count-allocs` to use it");
+            }
+            let options = RunOptions { timeout };
+            let start = Instant::now();
+            let reports: Vec<_> = match parallel {
+                // The days block their pool thread while waiting for their parts, so
+                // they get a pool of their own and leave the global one to the solvers.
+                true => rayon::ThreadPoolBuilder::new()
+                    .thread_name(|i| format!("day-runner-{i}"))
+                    .build()?
+                    .install(|| {
+                        solvers
+                            .into_par_iter()
+                            .map(|solver| runner::run_day(solver, &options))
+                            .collect()
+                    }),
+                false => solvers
+                    .into_iter()
+                    .map(|solver| runner::run_day(solver, &options))
+                    .collect(),
+            };
+            let wall_time = start.elapsed();
+            let stdout = std::io::stdout().lock();
+            match format {
+                Format::Text => {
+                    summary::write_summary(&reports, &AnswerDb::load(year)?, wall_time, stdout)?
+                }
+                format => output::write(format, &output::records(&reports), stdout)?,
+            }
+            let unsolved = reports.iter().filter(|report| !report.solved()).count();
+            if unsolved > 0 {
+                bail!("{unsolved} day(s) did not solve");
+            }
+        }
+        Command::Watch { day, timeout } => {
+            watch::watch(solver(day)?, &RunOptions { timeout })?;
+        }
+        Command::Fetch { days } => {
+            let client = Client::from_env()?;
+            for day in days {
+                let path = input::path(year, day);
+                match input::fetch(&client, year, day)? {
+                    true => tracing::info!(year, day, "downloaded {}", path.display()),
+                    false => tracing::info!(year, day, "{} is cached", path.display()),
+                }
+            }
+        }
+        Command::Submit { day, part, answer } => {
+            let answer = match answer {
+                Some(answer) => Answer(answer),
+                None => {
+                    let solver = solver(day)?;
+                    let parsed = solver.parse(&input::load(year, day)?)?;
+                    let report = runner::run_part(solver, &parsed, part, &Default::default());
+                    report
+                        .answer()
+                        .cloned()
+                        .context(report.status.to_string())?
+                }
+            };
+            let db_path = answers::path(year);
+            let mut db = AnswerDb::load_from(&db_path)?;
+            let client = Client::from_env()?;
+            let outcome = submit::submit(&client, &mut db, &db_path, year, day, part, &answer)?;
+            tracing::info!(year, day, part = %part, %answer, "{outcome}");
+            if !matches!(outcome, Outcome::Correct | Outcome::AlreadySolved) {
+                bail!("{answer} was not accepted: {outcome}");
+            }
+        }
+        Command::Leaderboard { file, format } => {
+            let board = Leaderboard::load(&file)?;
+            leaderboard::write_report(&board, format, std::io::stdout().lock())?;
+        }
+        Command::Serve { addr, timeout } => {
+            let timeout = Some(timeout.unwrap_or(serve::DEFAULT_TIMEOUT));
+            let service = Service {
+                registry,
+                year,
+                options: RunOptions { timeout },
+            };
+            serve::serve(&addr, service)?;
+        }
+        Command::Inspect { day } => {
+            let (report, parsed) = inspect::inspect(solver(day)?, &input::load(year, day)?);
+            report.write(std::io::stdout().lock())?;
+            parsed.context("Could not parse the input")?;
+        }
+        Command::Explore { day } => {
+            explore::explore(&record(solver(day)?)?)?;
+        }
+        Command::Record {
+            day,
+            output,
+            images,
+            scale,
+            fps,
+            every,
+            max_frames,
+        } => {
+            let scene = record(solver(day)?)?;
+            let options = CastOptions {
+                fps,
+                every,
+                max_frames,
+                title: Some(format!("{year} day {day}")),
+            };
+            if let Some(format) = images {
+                let dir = output.unwrap_or_else(|| format!("day-{day}-frames").into());
+                let steps = asciicast::sample(scene.steps().len(), &options);
+                let frames = steps
+                    .iter()
+                    .map(|&step| Image::from_scene(&scene, step).scaled(scale));
+                let paths = image::save_sequence(&dir, format, frames)?;
+                tracing::info!(day, "wrote {} frames to {}", paths.len(), dir.display());
+                return Ok(());
+            }
+            let output = output.unwrap_or_else(|| format!("day-{day}.cast").into());
+            let file = std::fs::File::create(&output)
+                .with_context(|| format!("Could not create {}", output.display()))?;
+            let frames = asciicast::write(&scene, &options, std::io::BufWriter::new(file))?;
+            tracing::info!(day, "wrote {frames} frames to {}", output.display());
+        }
+        Command::Generate { day, seed, size } => {
+            let seed = match seed {
+                Some(seed) => seed,
+                None => {
+                    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)?;
+                    now.as_nanos() as u64
+                }
+            };
+            tracing::info!(day, seed, size, "generating");
+            print!("{}", generate::generate(solver(day)?, seed, size)?);
+        }
+        Command::New { day } => {
+            if year != days::YEAR {
+                bail!("New days are added to {}, not {year}", days::YEAR);
+            }
+            let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+            for path in scaffold::new_day(root, day)? {
+                tracing::info!(day, "wrote {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+use nom::{
+    character::complete::{digit1, multispace1},
+    combinator::map_res,
+    sequence::tuple,
+    IResult,
+};
+
+use crate::{
+    cancel::Cancel,
+    generate::Rng,
+    solution::{Answer, Solution},
+    validate::{self, Diagnostic},
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 1;
+    type Input = Vec<(u32, u32)>;
+
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        for (y, line) in validate::lines(input) {
+            let numbers: Vec<&str> = line.split_whitespace().collect();
+            if numbers.len() != 2 {
+                diagnostics.push(Diagnostic::new(
+                    y,
+                    1,
+                    format!("expected 2 numbers, found {}", numbers.len()),
+                ));
+            }
+            for number in numbers {
+                if number.parse::<u32>().is_err() {
+                    let x = validate::column(line, number);
+                    diagnostics.push(Diagnostic::new(y, x, format!("{number:?} is not a number")));
+                }
+            }
+        }
+        diagnostics
+    }
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        let res = input
+            .lines()
+            .map(|line| parse_line(line).map(|(_, pair)| pair))
+            .inspect(|result| {
+                tracing::trace!(?result);
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_owned())?;
+        Ok(res)
+    }
+
+    fn part_1(input: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        Ok(part_1(input))
+    }
+
+    fn part_2(input: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+fn part_1(lines: &[(u32, u32)]) -> u64 {
+    let mut left: Vec<_> = lines.iter().map(|(a, _)| *a).collect();
+    let mut right: Vec<_> = lines.iter().map(|(_, b)| *b).collect();
+    left.sort_unstable();
+    right.sort_unstable();
+    assert!(left.len() == right.len());
+    left.iter()
+        .zip(right.iter())
+        .map(|(a, b)| u64::from(a.abs_diff(*b)))
+        .sum::<u64>()
+}
+
+fn part_2(lines: &[(u32, u32)]) -> anyhow::Result<u64> {
+    let right: HashMap<u32, u64> =
+        lines
+            .iter()
+            .map(|(_, b)| *b)
+            .fold(HashMap::default(), |mut acc, el| {
+                acc.entry(el).and_modify(|v| *v += 1).or_insert(1);
+                acc
+            });
+    lines
+        .iter()
+        .map(|(a, _)| *a)
+        .try_fold(0u64, |sum, a| {
+            let count = right.get(&a).cloned().unwrap_or_default();
+            count
+                .checked_mul(a.into())
+                .and_then(|similarity| sum.checked_add(similarity))
+        })
+        .context("The similarity score overflows a u64")
+}
+
+fn parse_line(line: &str) -> IResult<&str, (u32, u32)> {
+    map_res(
+        tuple((digit1, multispace1, digit1)),
+        |(a, _, b): (&str, &str, &str)| {
+            a.parse()
+                .ok()
+                .zip(b.parse().ok())
+                .context("Could not parse line")
+        },
+    )(line)
+}
+
+/// `size` pairs of 5 digit location ids. About a third of the right ids are
+/// also on the left, so that part 2 finds similarities.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let left: Vec<u64> = (0..size).map(|_| rng.between(10_000, 99_999)).collect();
+    let mut input = String::new();
+    for &id in &left {
+        let right = match rng.chance(0.3) {
+            true => *rng.pick(&left),
+            false => rng.between(10_000, 99_999),
+        };
+        input += &format!("{id}   {right}\n");
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solution::solve, Part};
+
+    #[test]
+    fn similarity_past_u32() {
+        let input = "4000000000   4000000000\n4000000000   4000000000\n";
+        let answer = solve::<Day1>(input, Part::Two).unwrap();
+        assert_eq!(answer.0, "16000000000");
+    }
+}
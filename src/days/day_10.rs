@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use anyhow::Context;
+
+use crate::{
+    cancel::{Cancel, Cancelled},
+    generate::Rng,
+    scene::{Highlight, Scene, Tile},
+    solution::{Answer, Solution},
+    validate::{self, Diagnostic},
+    Grid, Point,
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 10;
+    type Input = Grid<u8>;
+
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        validate::grid(input, |c| c.is_ascii_digit())
+    }
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_1(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_1(input, cancel)
+    }
+
+    fn part_2(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_2(input, cancel)
+    }
+
+    fn record(input: &Self::Input) -> Option<Scene> {
+        Some(record_trails(input))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+fn parse_input(input: &str) -> anyhow::Result<Grid<u8>> {
+    let grid = input
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| {
+                    let height = c
+                        .to_digit(10)
+                        .with_context(|| format!("{c:?} is not a height"))?;
+                    Ok(height as u8)
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        })
+        .collect::<anyhow::Result<_>>()?;
+    Ok(Grid(grid))
+}
+
+fn part_1(grid: &Grid<u8>, cancel: &Cancel) -> anyhow::Result<usize> {
+    let _span = tracing::info_span!("part_1").entered();
+    let score = trailheads(grid)
+        .map(|start| {
+            cancel.check()?;
+            Ok(walk_trails(grid, start, |_, _| {}).len())
+        })
+        .sum::<Result<usize, Cancelled>>()?;
+    Ok(score)
+}
+
+fn part_2(grid: &Grid<u8>, cancel: &Cancel) -> anyhow::Result<usize> {
+    let _span = tracing::info_span!("part_2").entered();
+    let rating = trailheads(grid)
+        .map(|start| {
+            cancel.check()?;
+            Ok(walk_trails(grid, start, |_, _| {}).values().sum::<usize>())
+        })
+        .sum::<Result<usize, Cancelled>>()?;
+    Ok(rating)
+}
+
+fn height(grid: &Grid<u8>, pos: Point) -> Option<u8> {
+    pos.to_indices().and_then(|pos| grid.get(pos)).copied()
+}
+
+fn trailheads(grid: &Grid<u8>) -> impl Iterator<Item = Point> + '_ {
+    grid.indexed_iter()
+        .filter(|(_, height)| **height == 0)
+        .map(|(pos, _)| Point::from_indices(pos))
+}
+
+/// Neighbours of `pos` that are exactly one step higher.
+fn climb(grid: &Grid<u8>, pos: Point) -> impl Iterator<Item = Point> + '_ {
+    let current = height(grid, pos);
+    Point::ORTHOGONAL
+        .into_iter()
+        .map(move |dir| pos + dir)
+        .filter(move |&next_pos| {
+            let next = height(grid, next_pos);
+            current
+                .zip(next)
+                .is_some_and(|(current, next)| next.checked_sub(current) == Some(1))
+        })
+}
+
+/// Walks every trail from `start` one height at a time (a BFS where each
+/// frontier holds the cells of a single height). Returns the reachable peaks
+/// with the number of distinct trails leading to each of them. `observe` sees
+/// every frontier with its height.
+fn walk_trails(
+    grid: &Grid<u8>,
+    start: Point,
+    mut observe: impl FnMut(u8, &HashMap<Point, usize>),
+) -> HashMap<Point, usize> {
+    let frontier = HashMap::from([(start, 1)]);
+    observe(0, &frontier);
+    (1..=9).fold(frontier, |frontier, height| {
+        let next = frontier
+            .into_iter()
+            .flat_map(|(pos, trails)| climb(grid, pos).map(move |next| (next, trails)))
+            .fold(HashMap::new(), |mut next, (pos, trails)| {
+                *next.entry(pos).or_default() += trails;
+                next
+            });
+        tracing::trace!(height, frontier = ?next.keys().collect::<Vec<_>>());
+        observe(height, &next);
+        next
+    })
+}
+
+/// Every frontier of [`walk_trails`] from every trailhead, the cells reached
+/// stay marked.
+fn record_trails(grid: &Grid<u8>) -> Scene {
+    let tiles = grid
+        .0
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&height| Tile::new((b'0' + height) as char, format!("height {height}")))
+                .collect()
+        })
+        .collect();
+    let mut scene = Scene::new(Grid(tiles));
+    for start in trailheads(grid) {
+        let Some((y, x)) = start.to_indices() else {
+            continue;
+        };
+        walk_trails(grid, start, |height, frontier| {
+            let step = scene.push(format!(
+                "trailhead ({y}, {x}), height {height}: {} cells, {} trails",
+                frontier.len(),
+                frontier.values().sum::<usize>()
+            ));
+            step.mark((y, x), Highlight::Current);
+            for pos in frontier.keys().filter_map(|pos| pos.to_indices()) {
+                step.mark(pos, Highlight::Frontier)
+                    .trail(pos, Highlight::Visited);
+            }
+        });
+    }
+    scene
+}
+
+/// A `size` by `size` map of random heights, with trails from 0 to 9 walked
+/// into it.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut heights: Vec<Vec<u8>> = (0..size)
+        .map(|_| (0..size).map(|_| rng.between(0, 9) as u8).collect())
+        .collect();
+    for _ in 0..size * size / 20 {
+        let (mut y, mut x) = (rng.below(size), rng.below(size));
+        for height in 0..=9 {
+            heights[y][x] = height;
+            let next: Vec<(usize, usize)> = [(0, 1), (0, -1), (1, 0), (-1, 0)]
+                .iter()
+                .filter_map(|&(dy, dx)| {
+                    Some((y.checked_add_signed(dy)?, x.checked_add_signed(dx)?))
+                })
+                .filter(|&(y, x)| y < size && x < size)
+                .collect();
+            if next.is_empty() {
+                break;
+            }
+            (y, x) = *rng.pick(&next);
+        }
+    }
+    heights
+        .iter()
+        .map(|row| row.iter().map(|h| (b'0' + h) as char).collect::<String>() + "\n")
+        .collect()
+}
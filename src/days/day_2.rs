@@ -0,0 +1,129 @@
+use crate::{
+    cancel::{Cancel, Cancelled},
+    generate::Rng,
+    iter::ArrayWindowsExt,
+    solution::{Answer, Solution},
+    validate::{self, Diagnostic},
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 2;
+    type Input = Vec<Vec<i32>>;
+
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        for (y, line) in validate::lines(input) {
+            if line.is_empty() {
+                diagnostics.push(Diagnostic::new(y, 1, "empty report"));
+                continue;
+            }
+            for level in line.split(' ') {
+                if level.parse::<i32>().is_err() {
+                    let x = validate::column(line, level);
+                    diagnostics.push(Diagnostic::new(y, x, format!("{level:?} is not a number")));
+                }
+            }
+        }
+        diagnostics
+    }
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        let reports = input
+            .lines()
+            .map(|line| line.split(' ').map(str::parse::<i32>).collect())
+            .collect::<Result<_, _>>()?;
+        Ok(reports)
+    }
+
+    fn part_1(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_1(input, cancel)
+    }
+
+    fn part_2(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_2(input, cancel)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+/// The first step between levels that breaks the rules, step `k` going from
+/// level `k` to level `k + 1`.
+fn find_report_error(report: &[i32]) -> Option<usize> {
+    let mut diffs = report
+        .iter()
+        .array_windows()
+        .map(|[&a, &b]| i64::from(b) - i64::from(a))
+        .map(|diff| (diff.signum(), matches!(diff.abs(), 1..=3)));
+    let first = diffs.next()?;
+    if !first.1 {
+        return Some(0);
+    }
+    diffs.position(|d| d != first).map(|i| i + 1)
+}
+
+fn part_1(reports: &[Vec<i32>], cancel: &Cancel) -> anyhow::Result<usize> {
+    let safe = reports
+        .iter()
+        .map(|report| {
+            cancel.check()?;
+            Ok(find_report_error(report).is_none() as usize)
+        })
+        .sum::<Result<usize, Cancelled>>()?;
+    Ok(safe)
+}
+
+fn part_2(reports: &[Vec<i32>], cancel: &Cancel) -> anyhow::Result<usize> {
+    let safe = reports
+        .iter()
+        .map(|report| {
+            cancel.check()?;
+            Ok(dampened_safe(report) as usize)
+        })
+        .sum::<Result<usize, Cancelled>>()?;
+    Ok(safe)
+}
+
+/// Whether the report is safe with at most one level dropped.
+fn dampened_safe(report: &[i32]) -> bool {
+    let Some(error) = find_report_error(report) else {
+        return true;
+    };
+    // the bad step can be fixed by dropping either of its levels, or the level
+    // before it when that one set the wrong direction
+    for error in error.saturating_sub(1)..=error + 1 {
+        let report = [&report[..error], &report[(error + 1)..]].concat();
+        if find_report_error(&report).is_none() {
+            return true;
+        }
+    }
+    false
+}
+
+/// `size` reports of 5 to 8 levels. They start out safe, then up to two levels
+/// are nudged, which leaves some safe, some fixable by the dampener and some
+/// unsafe.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let len = rng.between(5, 8) as usize;
+        let direction = if rng.chance(0.5) { 1 } else { -1 };
+        let mut levels = vec![rng.between(30, 70) as i64];
+        for _ in 1..len {
+            let step = rng.between(1, 3) as i64 * direction;
+            levels.push(levels[levels.len() - 1] + step);
+        }
+        for _ in 0..rng.below(3) {
+            let i = rng.below(len);
+            levels[i] += rng.between(0, 8) as i64 - 4;
+        }
+        let levels: Vec<String> = levels.iter().map(i64::to_string).collect();
+        input += &levels.join(" ");
+        input.push('\n');
+    }
+    input
+}
@@ -0,0 +1,156 @@
+use anyhow::bail;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take, take_while_m_n},
+    combinator::{map, map_res, value},
+    multi::many0,
+    sequence::{delimited, separated_pair},
+    IResult,
+};
+
+use crate::{
+    cancel::Cancel,
+    generate::Rng,
+    solution::{Answer, Solution},
+    validate::Diagnostic,
+};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 3;
+    type Input = String;
+
+    /// Corrupted memory can hold anything, as long as there is something to
+    /// multiply.
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        match scan(mul)(input) {
+            Ok((_, muls)) if !muls.is_empty() => vec![],
+            _ => vec![Diagnostic::new(1, 1, "no `mul(X,Y)` instructions")],
+        }
+    }
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part_1(input: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+/// `mul(X,Y)`, X and Y having 1 to 3 digits.
+fn mul(input: &str) -> IResult<&str, (i64, i64)> {
+    let number = |input| {
+        map_res(
+            take_while_m_n(1, 3, |c: char| c.is_ascii_digit()),
+            str::parse::<i64>,
+        )(input)
+    };
+    delimited(
+        tag("mul("),
+        separated_pair(number, tag(","), number),
+        tag(")"),
+    )(input)
+}
+
+/// Every match of `token` in `input`, skipping one character at a time
+/// between them rather than recursing, which overflowed the stack on long
+/// stretches of corrupted memory.
+fn scan<'a, T: Clone>(
+    token: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    map(
+        many0(alt((map(token, Some), value(None, take(1usize))))),
+        |tokens| tokens.into_iter().flatten().collect(),
+    )
+}
+
+fn part_1(input: &str) -> anyhow::Result<i64> {
+    let (_, muls) = scan(mul)(input).map_err(|e| e.to_owned())?;
+    if muls.is_empty() {
+        bail!("No muls found");
+    }
+    Ok(muls.iter().map(|(a, b)| a * b).sum())
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Do,
+    Dont,
+    Mul(i64, i64),
+}
+
+fn part_2(input: &str) -> anyhow::Result<i64> {
+    let token = alt((
+        map(mul, |(a, b)| Token::Mul(a, b)),
+        value(Token::Do, tag("do()")),
+        value(Token::Dont, tag("don't()")),
+    ));
+    let (_, muls) = scan(token)(input).map_err(|e| e.to_owned())?;
+    if muls.is_empty() {
+        bail!("No muls found");
+    }
+    tracing::info!(?muls);
+    let (_, sum) = muls
+        .iter()
+        .fold((Token::Do, 0), |(state, sum), token| match (state, token) {
+            (_, Token::Do) => (Token::Do, sum),
+            (_, Token::Dont) => (Token::Dont, sum),
+            (Token::Do, Token::Mul(a, b)) => (Token::Do, sum + a * b),
+            (Token::Dont, _) => (Token::Dont, sum),
+            (Token::Mul(_, _), Token::Mul(_, _)) => unreachable!(),
+        });
+    Ok(sum)
+}
+
+/// About `size` chunks of corrupted memory, 60 to a line: valid and broken
+/// `mul`s, `do()`, `don't()` and noise.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const NOISE: &[&str] = &[
+        "!", "@", "#", "$", "%", "^", "&", "*", "(", ")", "[", "]", "{", "}", "<", ">", ",", ";",
+        ":", "'", "?", "/", " ", "+", "-", "_", "~", "mul", "do", "don't", "select()", "from()",
+        "why()", "what()", "how()",
+    ];
+    // part 1 fails without a single `mul`
+    let mut input = format!("mul({},{})", rng.between(1, 999), rng.between(1, 999));
+    for i in 1..size {
+        if i % 60 == 0 {
+            input.push('\n');
+        }
+        let (a, b) = (rng.between(1, 999), rng.between(1, 999));
+        match rng.below(10) {
+            0..=3 => input += &format!("mul({a},{b})"),
+            4 => input += "do()",
+            5 => input += "don't()",
+            6 => {
+                let broken = [
+                    format!("mul({a},{b}]"),
+                    format!("mul({a}*{b})"),
+                    format!("mul ( {a} , {b} )"),
+                    format!("mul[{a},{b}]"),
+                    format!("mul({a}, {b})"),
+                    format!("mul({a},{b}!"),
+                    format!("mul({a}{b})"),
+                ];
+                input += rng.pick::<String>(&broken);
+            }
+            _ => {
+                for _ in 0..rng.between(1, 3) {
+                    input += rng.pick::<&str>(NOISE);
+                }
+            }
+        }
+    }
+    input.push('\n');
+    input
+}
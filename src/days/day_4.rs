@@ -0,0 +1,172 @@
+use crate::{
+    cancel::{Cancel, Cancelled},
+    generate::Rng,
+    solution::{Answer, Solution},
+    validate::{self, Diagnostic},
+};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 4;
+    type Input = Grid<char>;
+
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        validate::grid(input, |c| "XMAS".contains(c))
+    }
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.lines().map(|line| line.chars().collect()).collect())
+    }
+
+    fn part_1(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_1(input, cancel)
+    }
+
+    fn part_2(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_2(input, cancel)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+type Grid<T> = Vec<Vec<T>>;
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+fn search(grid: &Grid<char>, start: (isize, isize), words: &[&str]) -> usize {
+    words
+        .iter()
+        .map(|word| search_word(grid, start, word))
+        .sum()
+}
+
+fn search_word(grid: &Grid<char>, start: (isize, isize), word: &str) -> usize {
+    DIRECTIONS
+        .iter()
+        .map(|(dx, dy)| {
+            let found = word.chars().enumerate().all(|(i, c)| {
+                let letter = grid_get_at_offset(grid, start, (*dx * i as isize, *dy * i as isize));
+                letter == Some(&c)
+            });
+            if found {
+                1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+fn part_1(input: &Grid<char>, cancel: &Cancel) -> anyhow::Result<usize> {
+    let count = input
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            cancel.check()?;
+            Ok(row
+                .iter()
+                .enumerate()
+                .map(|(x, _)| search(input, (x as isize, y as isize), &["XMAS"]))
+                .sum::<usize>())
+        })
+        .sum::<Result<usize, Cancelled>>()?;
+    Ok(count)
+}
+
+fn part_2(input: &Grid<char>, cancel: &Cancel) -> anyhow::Result<usize> {
+    let count = input
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            cancel.check()?;
+            Ok(row
+                .iter()
+                .enumerate()
+                .map(|(x, _)| is_x_mas(input, (x as isize, y as isize)) as usize)
+                .sum::<usize>())
+        })
+        .sum::<Result<usize, Cancelled>>()?;
+    Ok(count)
+}
+
+fn grid_get_at_offset(
+    grid: &Grid<char>,
+    (x, y): (isize, isize),
+    (dx, dy): (isize, isize),
+) -> Option<&char> {
+    let x = x + dx;
+    let y = y + dy;
+    grid.get(y as usize).and_then(|row| row.get(x as usize))
+}
+
+fn is_x_mas(grid: &Grid<char>, pos: (isize, isize)) -> bool {
+    let diagonal_1 = [
+        grid_get_at_offset(grid, pos, (-1, -1)),
+        grid_get_at_offset(grid, pos, (0, 0)),
+        grid_get_at_offset(grid, pos, (1, 1)),
+    ];
+    let diagonal_2 = [
+        grid_get_at_offset(grid, pos, (1, -1)),
+        grid_get_at_offset(grid, pos, (0, 0)),
+        grid_get_at_offset(grid, pos, (-1, 1)),
+    ];
+    let mas = [Some(&'M'), Some(&'A'), Some(&'S')];
+    let mas_reversed = [Some(&'S'), Some(&'A'), Some(&'M')];
+    let mas_1 = diagonal_1 == mas || diagonal_1 == mas_reversed;
+    let mas_2 = diagonal_2 == mas || diagonal_2 == mas_reversed;
+    mas_1 && mas_2
+}
+
+/// A `size` by `size` grid of random `XMAS` letters, with words written in
+/// every direction and `X-MAS` crosses planted into it.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut grid: Grid<char> = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| *rng.pick(&['X', 'M', 'A', 'S']))
+                .collect()
+        })
+        .collect();
+    let inside = |pos: isize| (0..size as isize).contains(&pos);
+    for _ in 0..size * size / 20 {
+        let (y, x) = (rng.below(size) as isize, rng.below(size) as isize);
+        let (dy, dx) = *rng.pick(&DIRECTIONS);
+        if inside(y + 3 * dy) && inside(x + 3 * dx) {
+            for (i, c) in "XMAS".chars().enumerate() {
+                grid[(y + i as isize * dy) as usize][(x + i as isize * dx) as usize] = c;
+            }
+        }
+        if size >= 3 {
+            let (y, x) = (
+                rng.between(1, size as u64 - 2),
+                rng.between(1, size as u64 - 2),
+            );
+            let (y, x) = (y as usize, x as usize);
+            grid[y][x] = 'A';
+            for (dy, dx) in [(1, 1), (1, -1)] {
+                let (m, s) = match rng.chance(0.5) {
+                    true => ('M', 'S'),
+                    false => ('S', 'M'),
+                };
+                grid[(y as isize - dy) as usize][(x as isize - dx) as usize] = m;
+                grid[(y as isize + dy) as usize][(x as isize + dx) as usize] = s;
+            }
+        }
+    }
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
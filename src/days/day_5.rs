@@ -0,0 +1,293 @@
+use std::{cmp::Ordering, collections::HashMap, str::FromStr};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::map_res,
+    multi::many0,
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+
+use crate::{
+    cancel::{Cancel, Cancelled},
+    generate::Rng,
+    solution::{Answer, Solution},
+    validate::{self, Diagnostic},
+};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 5;
+    type Input = State;
+
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        let mut separator = None;
+        for (y, line) in validate::lines(input) {
+            if line.is_empty() {
+                match separator {
+                    None => separator = Some(y),
+                    Some(first) => diagnostics.push(Diagnostic::new(
+                        y,
+                        1,
+                        format!("extra blank line, the first one is on line {first}"),
+                    )),
+                }
+                continue;
+            }
+            let fields: Vec<&str> = match separator {
+                None => line.split('|').collect(),
+                Some(_) => line.split(',').collect(),
+            };
+            if separator.is_none() && fields.len() != 2 {
+                diagnostics.push(Diagnostic::new(y, 1, "expected a rule like `47|53`"));
+            }
+            for field in fields {
+                if field.parse::<u32>().is_err() {
+                    let x = validate::column(line, field);
+                    diagnostics.push(Diagnostic::new(y, x, format!("{field:?} is not a number")));
+                }
+            }
+        }
+        if separator.is_none() {
+            let y = input.lines().count() + 1;
+            diagnostics.push(Diagnostic::new(
+                y,
+                1,
+                "missing the blank line between rules and updates",
+            ));
+        }
+        diagnostics
+    }
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        get_problem_state(input)
+    }
+
+    fn part_1(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_1(input, cancel)
+    }
+
+    fn part_2(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_2(input, cancel)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule(u32, u32);
+
+fn parse_number(input: &str) -> Result<(&str, u32), nom::Err<nom::error::Error<&str>>> {
+    map_res(digit1, str::parse::<u32>)(input)
+}
+
+fn parse_rule(input: &str) -> IResult<&str, Rule> {
+    let (rem, res) = separated_pair(parse_number, tag("|"), parse_number)(input)?;
+    Ok((rem, Rule(res.0, res.1)))
+}
+
+impl FromStr for Rule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (_, rule) = parse_rule(s).map_err(|e| anyhow::anyhow!("{}", e))?;
+        Ok(rule)
+    }
+}
+
+fn parse_printed_updates(input: &str) -> IResult<&str, Vec<u32>> {
+    many0(alt((terminated(parse_number, tag(",")), parse_number)))(input)
+}
+
+trait Updates {
+    fn parse_updates(input: &str) -> anyhow::Result<Self>
+    where
+        Self: Sized;
+}
+
+impl Updates for Vec<u32> {
+    fn parse_updates(input: &str) -> anyhow::Result<Self> {
+        parse_printed_updates(input)
+            .map(|(_, updates)| updates)
+            .map_err(|e| anyhow::anyhow!("{}", e))
+    }
+}
+
+pub struct State {
+    updates: Vec<Vec<u32>>,
+    rules: Vec<Rule>,
+    rule_map: HashMap<u32, Vec<Rule>>,
+    update_map: Vec<HashMap<u32, usize>>,
+}
+
+fn get_problem_state(input: &str) -> anyhow::Result<State> {
+    let &[rules, updates, ..] = input.split("\n\n").collect::<Vec<_>>().as_slice() else {
+        anyhow::bail!("Invalid input");
+    };
+    let rules = rules
+        .lines()
+        .map(Rule::from_str)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let updates = updates
+        .lines()
+        .map(Vec::<u32>::parse_updates)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let rule_map = rules
+        .clone()
+        .into_iter()
+        .flat_map(|Rule(a, b)| {
+            let a = (
+                a,
+                rules
+                    .clone()
+                    .into_iter()
+                    .filter(move |Rule(x, y)| *x == a || *y == a)
+                    .collect(),
+            );
+            let b = (
+                b,
+                rules
+                    .clone()
+                    .into_iter()
+                    .filter(move |Rule(x, y)| *x == b || *y == b)
+                    .collect(),
+            );
+            std::iter::once(a).chain(std::iter::once(b))
+        })
+        .collect::<HashMap<u32, Vec<Rule>>>();
+    let update_map: Vec<HashMap<_, _>> = updates
+        .iter()
+        .map(|update| update.iter().enumerate().map(|(i, el)| (*el, i)).collect())
+        .collect();
+    Ok(State {
+        rules,
+        updates,
+        update_map,
+        rule_map,
+    })
+}
+
+fn ordered_correctly(
+    el: u32,
+    rule_map: &HashMap<u32, Vec<Rule>>,
+    update_map: &HashMap<u32, usize>,
+) -> bool {
+    let Some(required_rules) = rule_map.get(&el) else {
+        return true;
+    };
+    required_rules.iter().all(|Rule(a, b)| {
+        let a_position = update_map.get(a);
+        let b_position = update_map.get(b);
+        match (a_position, b_position) {
+            (Some(a), Some(b)) => a < b,
+            _ => true,
+        }
+    })
+}
+
+fn updates_ordered(
+    updates: &[u32],
+    update_map: &HashMap<u32, usize>,
+    rule_map: &HashMap<u32, Vec<Rule>>,
+) -> bool {
+    updates
+        .iter()
+        .all(|el| ordered_correctly(*el, rule_map, update_map))
+}
+
+fn part_1(state: &State, cancel: &Cancel) -> anyhow::Result<u32> {
+    let State {
+        updates,
+        update_map,
+        rule_map,
+        ..
+    } = state;
+    let sum = updates
+        .iter()
+        .zip(update_map.iter())
+        .map(|(updates, update_map)| {
+            cancel.check()?;
+            let ordered = updates_ordered(updates, update_map, rule_map);
+            Ok(if ordered {
+                updates[updates.len() / 2]
+            } else {
+                0
+            })
+        })
+        .sum::<Result<u32, Cancelled>>()?;
+    Ok(sum)
+}
+
+fn part_2(state: &State, cancel: &Cancel) -> anyhow::Result<u32> {
+    let State {
+        updates,
+        rules,
+        rule_map,
+        update_map,
+    } = state;
+    let mut updates = updates.clone();
+    let sum = updates
+        .iter_mut()
+        .zip(update_map.iter())
+        .filter_map(|(updates, update_map)| {
+            if updates_ordered(updates, update_map, rule_map) {
+                return None;
+            }
+            updates.sort_by(|a, b| {
+                let rule = rules
+                    .iter()
+                    .find(|Rule(x, y)| { a == x && b == y } || { a == y && b == x });
+                match rule {
+                    None => Ordering::Equal,
+                    Some(Rule(x, y)) if x == a && y == b => Ordering::Less,
+                    Some(Rule(x, y)) if x == b && y == a => Ordering::Greater,
+                    _ => unreachable!(),
+                }
+            });
+            Some(updates)
+        })
+        .map(|updates| {
+            cancel.check()?;
+            let middle = updates[updates.len() / 2];
+            tracing::info!(?middle);
+            Ok(middle)
+        })
+        .sum::<Result<u32, Cancelled>>()?;
+    Ok(sum)
+}
+
+/// `size` updates of an odd number of pages, about half of them in order. The
+/// rules order every pair of pages, like the real inputs, which use the same
+/// number of pages as updates up to 89.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut pages: Vec<u64> = (10..=99).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(size.clamp(3, 89));
+    let mut rules: Vec<String> = (0..pages.len())
+        .flat_map(|i| (i + 1..pages.len()).map(move |j| (i, j)))
+        .map(|(i, j)| format!("{}|{}", pages[i], pages[j]))
+        .collect();
+    rng.shuffle(&mut rules);
+    let longest = (pages.len().min(23) - 1) / 2;
+    let updates: Vec<String> = (0..size)
+        .map(|_| {
+            let len = 2 * rng.between(1, longest as u64) as usize + 1;
+            let mut update: Vec<usize> = (0..pages.len()).collect();
+            rng.shuffle(&mut update);
+            update.truncate(len);
+            if rng.chance(0.5) {
+                update.sort_unstable();
+            }
+            let update: Vec<String> = update.iter().map(|&i| pages[i].to_string()).collect();
+            update.join(",")
+        })
+        .collect();
+    format!("{}\n\n{}\n", rules.join("\n"), updates.join("\n"))
+}
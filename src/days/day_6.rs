@@ -0,0 +1,327 @@
+use std::{collections::HashSet, hash::Hash};
+
+use anyhow::{bail, Context};
+use tailcall::tailcall;
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::{
+    cancel::{Cancel, Cancelled},
+    generate::Rng,
+    progress::ProgressExt,
+    scene::{Highlight, Scene, Tile},
+    solution::{Answer, Solution},
+    validate::{self, Diagnostic},
+};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 6;
+    type Input = Grid<Cell>;
+
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = validate::grid(input, |c| ".#^v<>".contains(c));
+        let guards: Vec<(usize, usize)> = validate::lines(input)
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, c)| "^v<>".contains(*c))
+                    .map(move |(x, _)| (y, x + 1))
+            })
+            .collect();
+        match guards.as_slice() {
+            [] => diagnostics.push(Diagnostic::new(1, 1, "no guard in the map")),
+            [_] => {}
+            [_, extra @ ..] => diagnostics.extend(extra.iter().map(|&(y, x)| {
+                Diagnostic::new(
+                    y,
+                    x,
+                    format!("{} guards in the map, expected 1", guards.len()),
+                )
+            })),
+        }
+        diagnostics
+    }
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        get_grid(input)
+    }
+
+    fn part_1(input: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_2(input, cancel)
+    }
+
+    fn record(input: &Self::Input) -> Option<Scene> {
+        record_walk(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+type Grid<T> = Vec<Vec<T>>;
+
+fn get_input(input: &str) -> Grid<char> {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+type Direction = (isize, isize);
+
+const UP: Direction = (0, -1);
+const DOWN: Direction = (0, 1);
+const LEFT: Direction = (-1, 0);
+const RIGHT: Direction = (1, 0);
+
+pub trait DirectionExt {
+    fn turn_right(self) -> Self;
+}
+
+impl DirectionExt for Direction {
+    fn turn_right(self) -> Self {
+        match self {
+            UP => RIGHT,
+            DOWN => LEFT,
+            LEFT => UP,
+            RIGHT => DOWN,
+            _ => unreachable!("Direction not normalized"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Wall,
+    Guard(Direction),
+}
+
+fn parse_cell(c: char) -> anyhow::Result<Cell> {
+    Ok(match c {
+        '.' => Cell::Empty,
+        '#' => Cell::Wall,
+        '^' => Cell::Guard(UP),
+        'v' => Cell::Guard(DOWN),
+        '>' => Cell::Guard(RIGHT),
+        '<' => Cell::Guard(LEFT),
+        _ => bail!("Unexpected {c:?} in the map"),
+    })
+}
+
+impl Cell {
+    fn tile(self) -> Tile {
+        match self {
+            Cell::Empty => Tile::new('.', "empty"),
+            Cell::Wall => Tile::new('#', "wall"),
+            Cell::Guard(direction) => Tile::new(
+                arrow(direction),
+                format!("guard start, facing {}", arrow(direction)),
+            ),
+        }
+    }
+}
+
+fn arrow(direction: Direction) -> char {
+    match direction {
+        UP => '^',
+        DOWN => 'v',
+        LEFT => '<',
+        RIGHT => '>',
+        _ => unreachable!("Direction not normalized"),
+    }
+}
+
+impl TryFrom<char> for Cell {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        parse_cell(c)
+    }
+}
+
+fn get_grid(input: &str) -> anyhow::Result<Grid<Cell>> {
+    let input = get_input(input);
+    input
+        .iter()
+        .map(|row| row.iter().map(|c| (*c).try_into()).collect())
+        .collect()
+}
+
+fn find_guard(grid: &Grid<Cell>) -> Option<GuardState> {
+    grid.iter().enumerate().find_map(|(y, row)| {
+        row.iter().enumerate().find_map(|(x, cell)| match cell {
+            Cell::Guard(direction) => {
+                Some(GuardState(Position(x as isize, y as isize), *direction))
+            }
+            _ => None,
+        })
+    })
+}
+
+fn part_1(grid: &Grid<Cell>) -> anyhow::Result<usize> {
+    let guard = find_guard(grid).context("No guard found")?;
+    let visited = simulate(grid, guard, HashSet::new())?;
+    Ok(get_unique_positions(&visited).len())
+}
+
+/// Where the guard goes from a state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Move(GuardState),
+    Leave,
+    /// Walls on every side, the guard turns on the spot forever.
+    BoxedIn,
+}
+
+fn find_next(grid: &Grid<Cell>, GuardState(Position(x, y), dir): GuardState, count: usize) -> Step {
+    if count >= 4 {
+        return Step::BoxedIn;
+    }
+    let next = (x + dir.0, y + dir.1);
+    let next_cell = grid
+        .get(next.1 as usize)
+        .and_then(|row| row.get(next.0 as usize));
+    match next_cell {
+        Some(Cell::Empty) | Some(Cell::Guard(_)) => {
+            Step::Move(GuardState(Position::from(next), dir))
+        }
+        Some(Cell::Wall) => find_next(
+            grid,
+            GuardState(Position(x, y), dir.turn_right()),
+            count + 1,
+        ),
+        None => Step::Leave,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position(isize, isize);
+
+impl From<(isize, isize)> for Position {
+    fn from(pos: (isize, isize)) -> Self {
+        Position(pos.0, pos.1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GuardState(Position, Direction);
+
+#[derive(Error, Debug)]
+#[error("Loop found")]
+pub struct LoopError;
+
+#[tailcall]
+fn simulate(
+    grid: &Grid<Cell>,
+    guard_state: GuardState,
+    mut visited: HashSet<GuardState>,
+) -> Result<HashSet<GuardState>, LoopError> {
+    let already_visited = visited.contains(&guard_state);
+    visited.insert(guard_state);
+    let next_step = find_next(grid, guard_state, 0);
+    match (already_visited, next_step) {
+        (true, _) | (false, Step::BoxedIn) => Err(LoopError),
+        (false, Step::Move(next_guard_state)) => simulate(grid, next_guard_state, visited),
+        (false, Step::Leave) => Ok(visited),
+    }
+}
+
+/// The walk of [`simulate`] one move at a time, the visited cells stay marked.
+fn record_walk(grid: &Grid<Cell>) -> Option<Scene> {
+    let tiles = grid
+        .iter()
+        .map(|row| row.iter().map(|cell| cell.tile()).collect())
+        .collect();
+    let mut scene = Scene::new(crate::Grid(tiles));
+    let mut guard = find_guard(grid)?;
+    let mut visited = HashSet::new();
+    loop {
+        let GuardState(Position(x, y), direction) = guard;
+        let pos = (y as usize, x as usize);
+        let looped = !visited.insert(guard);
+        let next = find_next(grid, guard, 0);
+        let label = match (looped, next) {
+            (true, _) => format!("({x}, {y}) again, loop found"),
+            (false, Step::BoxedIn) => format!("({x}, {y}) boxed in, loop found"),
+            (false, _) => format!("({x}, {y}) facing {}", arrow(direction)),
+        };
+        scene
+            .push(label)
+            .mark_glyph(pos, Highlight::Current, arrow(direction))
+            .trail(pos, Highlight::Visited);
+        match next {
+            Step::Move(next) if !looped => guard = next,
+            _ => return Some(scene),
+        }
+    }
+}
+
+fn get_unique_positions(visited: &HashSet<GuardState>) -> HashSet<&Position> {
+    visited
+        .iter()
+        .map(|GuardState(pos, _)| pos)
+        .collect::<HashSet<_>>()
+}
+
+fn part_2(grid: &Grid<Cell>, cancel: &Cancel) -> anyhow::Result<usize> {
+    let guard = find_guard(grid).context("No guard found")?;
+    let visited = simulate(grid, guard, HashSet::new())?;
+    let visited = get_unique_positions(&visited);
+
+    tracing::info!("Checking for loops");
+    let possible_obstacles = visited
+        .into_iter()
+        .progress("loop check")
+        .filter(|pos| pos != &&guard.0)
+        .map(|pos| {
+            cancel.check()?;
+            Ok(check_loop(grid, guard, *pos) as usize)
+        })
+        .sum::<Result<usize, Cancelled>>()?;
+    Ok(possible_obstacles)
+}
+
+#[instrument]
+fn check_loop(grid: &Grid<Cell>, guard_state: GuardState, path_pos: Position) -> bool {
+    let new_grid = {
+        let mut new_grid = grid.clone();
+        new_grid[path_pos.1 as usize][path_pos.0 as usize] = Cell::Wall;
+        new_grid
+    };
+    let visited = simulate(&new_grid, guard_state, HashSet::new());
+    matches!(visited, Err(LoopError))
+}
+
+/// A `size` by `size` lab with about 5% walls and a guard that walks out of it,
+/// since part 1 fails when the guard loops.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    loop {
+        let mut grid: Grid<Cell> = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| match rng.chance(0.05) {
+                        true => Cell::Wall,
+                        false => Cell::Empty,
+                    })
+                    .collect()
+            })
+            .collect();
+        let (y, x) = (rng.below(size), rng.below(size));
+        let direction = *rng.pick(&[UP, DOWN, LEFT, RIGHT]);
+        grid[y][x] = Cell::Guard(direction);
+        let guard = GuardState(Position(x as isize, y as isize), direction);
+        if simulate(&grid, guard, HashSet::new()).is_ok() {
+            return grid
+                .iter()
+                .map(|row| row.iter().map(|cell| cell.tile().glyph).collect::<String>() + "\n")
+                .collect();
+        }
+    }
+}
@@ -0,0 +1,369 @@
+use anyhow::{anyhow, bail, Context};
+use nom::{
+    bytes::complete::tag,
+    character::complete::digit1,
+    combinator::{map, map_res},
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult,
+};
+use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
+use tailcall::tailcall;
+use tracing::Level;
+
+use crate::{
+    cancel::Cancel,
+    generate::Rng,
+    inspect::{range, Report},
+    memo::CacheStats,
+    progress::ParallelProgressExt,
+    solution::{Answer, Solution},
+    validate::{self, Diagnostic},
+};
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 7;
+    type Input = Vec<Numbers>;
+
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+        for (y, line) in validate::lines(input) {
+            let Some((result, numbers)) = line.split_once(": ") else {
+                diagnostics.push(Diagnostic::new(
+                    y,
+                    1,
+                    "expected an equation like `190: 10 19`",
+                ));
+                continue;
+            };
+            let numbers: Vec<&str> = numbers.split(' ').collect();
+            if !(2..=MAX_NUMBERS).contains(&numbers.len()) {
+                diagnostics.push(Diagnostic::new(
+                    y,
+                    1,
+                    format!(
+                        "expected 2 to {MAX_NUMBERS} numbers, found {}",
+                        numbers.len()
+                    ),
+                ));
+            }
+            for number in [result].into_iter().chain(numbers) {
+                if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+                    let x = validate::column(line, number);
+                    diagnostics.push(Diagnostic::new(y, x, format!("{number:?} is not a number")));
+                } else if number.parse::<u64>().is_err() {
+                    let x = validate::column(line, number);
+                    diagnostics.push(Diagnostic::new(y, x, format!("{number} is too large")));
+                }
+            }
+        }
+        diagnostics
+    }
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        let (_, numbers) = parse_input(input).map_err(|e| e.to_owned())?;
+        Ok(numbers)
+    }
+
+    fn part_1(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_1(input, cancel)
+    }
+
+    fn part_2(input: &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_2(input, cancel)
+    }
+
+    fn inspect(numbers: &Self::Input, report: &mut Report) {
+        let prunable = numbers
+            .iter()
+            .filter(|numbers| {
+                // an overflowing sum is past any result
+                let first_two = numbers
+                    .numbers
+                    .iter()
+                    .take(2)
+                    .try_fold(0u64, |sum, &n| sum.checked_add(n));
+                numbers.numbers.iter().any(|&n| n > numbers.result)
+                    || first_two.is_none_or(|sum| sum > numbers.result)
+            })
+            .count();
+        let lengths = numbers.iter().map(|numbers| numbers.numbers.len());
+        let results = numbers.iter().map(|numbers| numbers.result);
+        report
+            .add("equations", numbers.len())
+            .add(
+                "operands",
+                range(
+                    lengths.clone().min().unwrap_or(0),
+                    lengths.max().unwrap_or(0),
+                ),
+            )
+            .add(
+                "results",
+                range(
+                    results.clone().min().unwrap_or(0),
+                    results.max().unwrap_or(0),
+                ),
+            )
+            .add("trivially prunable", prunable);
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+/// The most numbers in an equation, the memo keys have room for two bits per
+/// operator above the operator count.
+const MAX_NUMBERS: usize = 29;
+
+#[derive(Debug)]
+pub struct Numbers {
+    result: u64,
+    numbers: Vec<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum Operator {
+    Add,
+    Multiply,
+    Concat,
+}
+
+impl Operator {
+    fn into_key(ops: &[Operator]) -> u64 {
+        ops.iter()
+            .enumerate()
+            .fold(ops.len() as u64, |acc, (i, op)| match op {
+                Operator::Add => acc,
+                Operator::Multiply => acc | (1 << (2 * i + 8)),
+                Operator::Concat => acc | 1 << (2 * i + 9),
+            })
+    }
+}
+
+impl Operator {
+    /// `None` when the result does not fit, which makes it larger than any
+    /// equation's result.
+    fn apply(&self, a: u64, b: u64) -> Option<u64> {
+        match self {
+            Self::Add => a.checked_add(b),
+            Self::Multiply => a.checked_mul(b),
+            Self::Concat => {
+                let digits = b.checked_ilog10().unwrap_or(0) + 1;
+                a.checked_mul(10u64.checked_pow(digits)?)?.checked_add(b)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Equation<'a> {
+    numbers: &'a Numbers,
+    operators: &'a [Operator],
+}
+
+impl Equation<'_> {
+    fn calculate(&self) -> anyhow::Result<u64> {
+        let first = *self.numbers.numbers.first().context("No numbers")?;
+        let res = self
+            .numbers
+            .numbers
+            .iter()
+            .skip(1)
+            .zip(self.operators.iter())
+            .try_fold(first, |a, (b, op)| op.apply(a, *b))
+            .context("Overflow")?;
+        Ok(res)
+    }
+}
+
+fn solve_both(numbers: &[Numbers], allowed: &[Operator], cancel: &Cancel) -> anyhow::Result<u64> {
+    let (sum, cache_stats) = numbers
+        .par_iter()
+        .enumerate()
+        .progress("try_solve")
+        .map(|(i, numbers)| {
+            cancel.check()?;
+            let span = tracing::span!(Level::INFO, "", i = i);
+            let _span = span.enter();
+            let mut memo = Memo::new();
+            let res = try_solve(
+                numbers,
+                allowed,
+                None,
+                Some(SharedMemo::MutBorrow(&mut memo)),
+            );
+            anyhow::Ok((res.unwrap_or(0), memo.into_stats()))
+        })
+        .try_reduce(
+            || (0, CacheStats::default()),
+            |acc, el| {
+                let sum = acc
+                    .0
+                    .checked_add(el.0)
+                    .context("The total overflows a u64")?;
+                Ok((sum, acc.1.compose(&el.1)))
+            },
+        )?;
+    cache_stats.report("try_solve");
+    Ok(sum)
+}
+
+fn part_1(numbers: &[Numbers], cancel: &Cancel) -> anyhow::Result<u64> {
+    solve_both(numbers, &[Operator::Add, Operator::Multiply], cancel)
+}
+
+fn part_2(numbers: &[Numbers], cancel: &Cancel) -> anyhow::Result<u64> {
+    solve_both(
+        numbers,
+        &[Operator::Add, Operator::Multiply, Operator::Concat],
+        cancel,
+    )
+}
+
+type Memo = crate::memo::Memo<u64, u64>;
+type SharedMemo<'a> = crate::memo::SharedMemo<'a, u64, u64>;
+
+#[tailcall]
+fn try_solve(
+    numbers: &Numbers,
+    allowed: &[Operator],
+    operators: Option<&[Operator]>,
+    memo: Option<SharedMemo<'_>>,
+) -> anyhow::Result<u64> {
+    let required_operators = numbers.numbers.len() - 1;
+    let operators = operators.unwrap_or(&[]);
+    let mut memo = memo.unwrap_or_else(|| SharedMemo::Owned(Memo::new()));
+    tracing::trace!(?memo);
+    match (allowed, operators) {
+        (&[], _) => bail!("No solution"),
+        (_, ops @ [tail @ .., last]) if ops.len() == required_operators => {
+            let equation = Equation {
+                numbers,
+                operators: tail,
+            };
+            let key = Operator::into_key(tail);
+            let res = memo
+                .as_ref()
+                .get(&key)
+                .cloned()
+                .or_else(|| {
+                    tracing::trace!("cache miss");
+                    equation.calculate().ok()
+                })
+                .context("No solution")?;
+            let rhs = numbers.numbers.last().context("No numbers")?;
+            let res = last.apply(res, *rhs);
+            if res == Some(numbers.result) {
+                Ok(numbers.result)
+            } else {
+                Err(anyhow!("No solution"))
+            }
+        }
+        #[allow(clippy::manual_try_fold)]
+        (_, ops) => allowed
+            .iter()
+            .fold(Err(anyhow!("No solution")), move |acc, op| {
+                let new_ops = &[ops, &[*op]].concat();
+                let equation = Equation {
+                    numbers,
+                    operators: ops,
+                };
+                let key = Operator::into_key(ops);
+                let res = memo
+                    .as_ref()
+                    .get(&key)
+                    .cloned()
+                    .or_else(|| {
+                        tracing::trace!("cache miss");
+                        equation.calculate().ok()
+                    })
+                    .context("No solution")?;
+                let rhs = numbers.numbers[new_ops.len()];
+                let Some(res) = op.apply(res, rhs) else {
+                    return acc;
+                };
+                let key = Operator::into_key(new_ops);
+                memo.as_mut().insert(key, res);
+                if res > numbers.result {
+                    return acc;
+                }
+                let memo = memo.as_mut();
+                acc.or_else(|_| {
+                    try_solve(
+                        numbers,
+                        allowed,
+                        Some(new_ops),
+                        Some(SharedMemo::MutBorrow(memo)),
+                    )
+                })
+            }),
+    }
+}
+
+fn parse_input(input: &str) -> IResult<&str, Vec<Numbers>> {
+    separated_list1(tag("\n"), parse_numbers)(input)
+}
+
+fn parse_numbers(input: &str) -> IResult<&str, Numbers> {
+    let number = |input| map_res(digit1, str::parse::<u64>)(input);
+    map(
+        separated_pair(number, tag(": "), separated_list1(tag(" "), number)),
+        |(result, numbers)| Numbers { result, numbers },
+    )(input)
+}
+
+/// `size` equations of 2 to 8 numbers below 100. About half of them are made
+/// true with a random mix of `+`, `*` and `||`.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let count = rng.between(2, 8);
+        let numbers: Vec<u64> = (0..count).map(|_| rng.between(1, 99)).collect();
+        let solved = match rng.chance(0.5) {
+            true => numbers[1..]
+                .iter()
+                .try_fold(numbers[0], |acc, &n| match rng.below(3) {
+                    0 => acc.checked_add(n),
+                    1 => acc.checked_mul(n),
+                    _ => acc
+                        .checked_mul(10u64.pow(n.ilog10() + 1))
+                        .and_then(|acc| acc.checked_add(n)),
+                }),
+            false => None,
+        };
+        let result = solved.unwrap_or_else(|| rng.between(1, 1_000_000));
+        let numbers: Vec<String> = numbers.iter().map(u64::to_string).collect();
+        input += &format!("{result}: {}\n", numbers.join(" "));
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solution::solve, Part};
+
+    #[test]
+    fn total_past_u64() {
+        let input = "18446744073709551615: 18446744073709551615 0\n".repeat(2);
+        for part in Part::ALL {
+            let error = solve::<Day7>(&input, part).unwrap_err();
+            assert_eq!(error.to_string(), "The total overflows a u64");
+        }
+    }
+
+    #[test]
+    fn inspect_past_u64() {
+        let numbers = Day7::parse("18446744073709551615: 18446744073709551615 1\n").unwrap();
+        let mut report = Report::default();
+        Day7::inspect(&numbers, &mut report);
+        assert!(report
+            .stats()
+            .any(|stat| stat == ("trivially prunable", "1")));
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    cancel::Cancel,
+    generate::Rng,
+    solution::{Answer, Solution},
+    validate::{self, Diagnostic},
+    Grid, Point,
+};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 8;
+    type Input = Grid<Tile>;
+
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        validate::grid(input, |c| c == '.' || c.is_ascii_alphanumeric())
+    }
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part_1(input: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_1(input)
+    }
+
+    fn part_2(input: &Self::Input, _: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Tile {
+    Empty,
+    Antenna(char),
+}
+
+fn parse_input(input: &str) -> Grid<Tile> {
+    let grid = input
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| match c {
+                    '.' => Tile::Empty,
+                    ch => Tile::Antenna(ch),
+                })
+                .collect()
+        })
+        .collect();
+    Grid(grid)
+}
+
+fn map_antennas(grid: &Grid<Tile>) -> HashMap<char, Vec<(isize, isize)>> {
+    grid.indexed_iter()
+        .flat_map(|((i, j), tile)| match tile {
+            Tile::Empty => None,
+            Tile::Antenna(ch) => Some((*ch, (i as isize, j as isize))),
+        })
+        .fold(HashMap::new(), |mut map, (ch, pos)| {
+            map.entry(ch).or_default().push(pos);
+            map
+        })
+}
+
+enum Resonance {
+    One,
+    Infinite,
+}
+
+fn part_1(grid: &Grid<Tile>) -> anyhow::Result<usize> {
+    solve(grid, Resonance::One)
+}
+
+fn part_2(grid: &Grid<Tile>) -> anyhow::Result<usize> {
+    solve(grid, Resonance::Infinite)
+}
+
+fn solve(grid: &Grid<Tile>, resonance: Resonance) -> anyhow::Result<usize> {
+    let antinodes = antinodes(grid, resonance);
+    tracing::debug!("antinodes:\n{}", render(grid, &antinodes));
+    Ok(antinodes.len())
+}
+
+fn antinodes(grid: &Grid<Tile>, resonance: Resonance) -> HashSet<(usize, usize)> {
+    let map = map_antennas(grid);
+    map.values()
+        .flat_map(|positions| {
+            tracing::trace!(?positions);
+            positions
+                .iter()
+                .cloned()
+                .flat_map(|a| {
+                    positions
+                        .iter()
+                        .cloned()
+                        .filter(move |b| a != *b)
+                        .map(move |b| {
+                            let diff = (b.0 - a.0, b.1 - a.1);
+                            Line { point: a, diff }
+                        })
+                })
+                .flat_map(|Line { point, diff }| {
+                    // in resonance, every cell in line counts, including the
+                    // ones between steps of the full distance
+                    let diff = match resonance {
+                        Resonance::One => diff,
+                        Resonance::Infinite => {
+                            let gcd = gcd(diff.0, diff.1);
+                            (diff.0 / gcd, diff.1 / gcd)
+                        }
+                    };
+                    let p_0 = Point::from(point);
+                    let diff = Point::from(diff);
+                    let p_1 = p_0 + diff;
+                    let mut idx = match &resonance {
+                        Resonance::One => 1,
+                        Resonance::Infinite => 0,
+                    };
+                    let point_iter = std::iter::repeat_with(move || {
+                        let p_0_next = p_0 - diff * idx;
+                        let p_1_next = p_1 + diff * idx;
+                        idx += 1;
+                        (p_0_next, p_1_next)
+                    });
+                    match resonance {
+                        Resonance::One => point_iter.take(1).collect::<Vec<_>>(),
+                        Resonance::Infinite => point_iter
+                            .take_while(|&(p_0, p_1)| {
+                                grid.bounds_check(p_0) || grid.bounds_check(p_1)
+                            })
+                            .collect::<Vec<_>>(),
+                    }
+                })
+        })
+        .flat_map(|(p_0, p_1)| Some(p_0).into_iter().chain(Some(p_1)))
+        .filter_map(Point::to_indices)
+        .filter(|&pos| grid.bounds_check(pos))
+        .inspect(|antinode| tracing::trace!(?antinode))
+        .collect()
+}
+
+/// The map with `#` on the antinodes that are not antennas.
+fn render(grid: &Grid<Tile>, antinodes: &HashSet<(usize, usize)>) -> String {
+    let mut map = String::new();
+    for (i, row) in grid.0.iter().enumerate() {
+        for (j, tile) in row.iter().enumerate() {
+            map.push(match (tile, antinodes.contains(&(i, j))) {
+                (Tile::Empty, false) => '.',
+                (Tile::Empty, true) => '#',
+                (Tile::Antenna(ch), _) => *ch,
+            });
+        }
+        map.push('\n');
+    }
+    map
+}
+
+struct Line {
+    point: (isize, isize),
+    diff: (isize, isize),
+}
+
+fn gcd(a: isize, b: isize) -> isize {
+    match b {
+        0 => a.abs(),
+        _ => gcd(b, a % b),
+    }
+}
+
+/// A `size` by `size` map with about `size / 4` frequencies of 2 to 4
+/// antennas each.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const FREQUENCIES: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    let mut grid = vec![vec!['.'; size]; size];
+    if size > 0 {
+        for &frequency in &FREQUENCIES[..(size / 4).clamp(1, FREQUENCIES.len())] {
+            for _ in 0..rng.between(2, 4) {
+                // occupied cells are skipped rather than retried, to stay
+                // finite on tiny maps
+                let (y, x) = (rng.below(size), rng.below(size));
+                if grid[y][x] == '.' {
+                    grid[y][x] = frequency as char;
+                }
+            }
+        }
+    }
+    grid.iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+    #[test]
+    fn antinode_maps() {
+        let grid = parse_input(EXAMPLE);
+        let one = antinodes(&grid, Resonance::One);
+        aoc_core::assert_snapshot!("day_8_antinodes_part_1", render(&grid, &one));
+        let infinite = antinodes(&grid, Resonance::Infinite);
+        aoc_core::assert_snapshot!("day_8_antinodes_part_2", render(&grid, &infinite));
+    }
+}
@@ -0,0 +1,324 @@
+use std::fmt::Display;
+
+use nom::{
+    character::complete::anychar,
+    combinator::{map, map_opt, opt},
+    multi::many0,
+    sequence::pair,
+    IResult,
+};
+use tracing::instrument;
+
+use crate::{
+    cancel::Cancel,
+    generate::Rng,
+    scene::{Highlight, Scene, Tile},
+    solution::{Answer, Solution},
+    validate::{self, Diagnostic},
+    Grid,
+};
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const YEAR: u16 = super::YEAR;
+    const DAY: u8 = 9;
+    type Input = (Vec<Entry>, usize);
+
+    fn validate(input: &str) -> Vec<Diagnostic> {
+        let mut lines = validate::lines(input);
+        let Some((_, map)) = lines.next().filter(|(_, map)| !map.is_empty()) else {
+            return vec![Diagnostic::new(1, 1, "empty disk map")];
+        };
+        let mut diagnostics: Vec<Diagnostic> = map
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !c.is_ascii_digit())
+            .map(|(x, c)| Diagnostic::new(1, x + 1, format!("unexpected {c:?}")))
+            .collect();
+        diagnostics.extend(
+            lines
+                .filter(|(_, line)| !line.is_empty())
+                .map(|(y, _)| Diagnostic::new(y, 1, "the disk map is a single line")),
+        );
+        diagnostics
+    }
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_1(
+        (entries, disk_usage): &Self::Input,
+        _: &Cancel,
+    ) -> anyhow::Result<impl Into<Answer>> {
+        Ok(part_1(entries, *disk_usage))
+    }
+
+    fn part_2((entries, _): &Self::Input, cancel: &Cancel) -> anyhow::Result<impl Into<Answer>> {
+        part_2(entries, cancel)
+    }
+
+    fn record((entries, _): &Self::Input) -> Option<Scene> {
+        Some(record_compaction(entries))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Entry {
+    id: usize,
+    data: usize,
+    empty: usize,
+}
+
+fn parse_entries(input: &str) -> IResult<&str, Vec<Entry>> {
+    let take_digit = |input| map_opt(anychar, |c| c.to_digit(10).map(|d| d as usize))(input);
+    let mut id = 0;
+    let entry = map(pair(take_digit, opt(take_digit)), |(data, empty)| {
+        id += 1;
+        Entry {
+            id: id - 1,
+            data,
+            empty: empty.unwrap_or(0),
+        }
+    });
+    #[allow(clippy::let_and_return)]
+    let res = many0(entry)(input);
+    res
+}
+
+fn parse_input(input: &str) -> anyhow::Result<(Vec<Entry>, usize)> {
+    let (_, entries) = parse_entries(input).map_err(|e| e.to_owned())?;
+    let disk_usage = entries.iter().map(|entry| entry.data).sum();
+    Ok((entries, disk_usage))
+}
+
+/// Gaps are filled from the front with the last file blocks, so the first
+/// `disk_usage` positions end up holding every file block.
+#[instrument(skip(entries, disk_usage))]
+fn part_1(entries: &[Entry], disk_usage: usize) -> usize {
+    tracing::trace!(entries = ?entries);
+    let mut from_back = entries
+        .iter()
+        .rev()
+        .flat_map(|entry| std::iter::repeat_n(entry.id, entry.data));
+    entries
+        .iter()
+        .flat_map(|entry| {
+            std::iter::repeat_n(Some(entry.id), entry.data)
+                .chain(std::iter::repeat_n(None, entry.empty))
+        })
+        .take(disk_usage)
+        .map(|block| block.or_else(|| from_back.next()).unwrap_or(0))
+        .enumerate()
+        .map(|(pos, id)| pos * id)
+        .sum()
+}
+
+#[derive(Debug, Clone)]
+enum Block {
+    Empty { size: usize },
+    Data { id: usize, size: usize },
+}
+
+impl Block {
+    fn size(&self) -> usize {
+        match self {
+            Block::Empty { size } => *size,
+            Block::Data { size, .. } => *size,
+        }
+    }
+    fn size_mut(&mut self) -> &mut usize {
+        match self {
+            Block::Empty { size } => size,
+            Block::Data { size, .. } => size,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Disk(Vec<Block>);
+
+impl Disk {
+    fn new(entries: &[Entry]) -> Self {
+        let disk = entries
+            .iter()
+            .flat_map(|entry| {
+                [
+                    Block::Data {
+                        id: entry.id,
+                        size: entry.data,
+                    },
+                    Block::Empty { size: entry.empty },
+                ]
+                .into_iter()
+            })
+            .filter(|block| block.size() != 0)
+            .collect();
+        Self(disk)
+    }
+    fn find_block(&self, id: usize) -> Option<usize> {
+        self.0.iter().position(|block| match block {
+            Block::Data { id: block_id, .. } => id == *block_id,
+            Block::Empty { .. } => false,
+        })
+    }
+    fn arrange_block(mut self, block_id: usize) -> Self {
+        let unchanged = self.clone();
+        let arrange = || {
+            let block_idx = self.find_block(block_id)?;
+            let block = self.0.remove(block_idx);
+            let new_position = self.0.iter().take(block_idx).position(
+                |other| matches!(other, Block::Empty { size } if size >= &block.size()),
+            )?;
+            let previous = block_idx.checked_sub(1).and_then(|prev_idx| {
+                self.0
+                    .get_mut(prev_idx)
+                    .map(|prev_block| (prev_idx, prev_block))
+            });
+            match previous {
+                Some((_, Block::Empty { size })) => {
+                    *size += block.size();
+                }
+                Some((prev_idx, Block::Data { .. })) => {
+                    let empty_space = block.size();
+                    self.0
+                        .insert(prev_idx + 1, Block::Empty { size: empty_space });
+                }
+                None => {}
+            }
+            let mut empty_block = self.0.remove(new_position);
+            *empty_block.size_mut() = empty_block.size().saturating_sub(block.size());
+            self.0.insert(new_position, block);
+            if empty_block.size() > 0 {
+                self.0.insert(new_position + 1, empty_block);
+            }
+            Some(self)
+        };
+        arrange().unwrap_or(unchanged)
+    }
+    /// The position of the first block of file `id`.
+    fn offset(&self, id: usize) -> Option<usize> {
+        let index = self.find_block(id)?;
+        Some(self.0[..index].iter().map(Block::size).sum())
+    }
+    fn iter(&self) -> impl Iterator<Item = Option<usize>> + '_ {
+        self.0.iter().flat_map(|block| match block {
+            Block::Empty { size } => std::iter::repeat(None).take(*size),
+            Block::Data { id, size } => std::iter::repeat(Some(*id)).take(*size),
+        })
+    }
+}
+
+impl Display for Disk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = self
+            .iter()
+            .map(|id| id.map(|id| id.to_string()).unwrap_or(".".to_string()))
+            .collect::<String>();
+        write!(f, "{s}")
+    }
+}
+
+/// Moves every file once, from the highest id down, to the first gap before
+/// it that fits.
+fn compact(entries: &[Entry], cancel: &Cancel) -> anyhow::Result<Disk> {
+    let disk = Disk::new(entries);
+    entries
+        .iter()
+        .rev()
+        .map(|entry| entry.id)
+        .try_fold(disk, |disk, id| {
+            cancel.check()?;
+            anyhow::Ok(disk.arrange_block(id))
+        })
+}
+
+#[instrument(skip(entries, cancel))]
+fn part_2(entries: &[Entry], cancel: &Cancel) -> anyhow::Result<usize> {
+    let disk = compact(entries, cancel)?;
+    Ok(disk
+        .iter()
+        .enumerate()
+        .map(|(pos, id)| pos * id.unwrap_or(0))
+        .sum())
+}
+
+/// Blocks per row when the disk is drawn as a grid.
+const DISK_WIDTH: usize = 64;
+
+/// The compaction of part 2 one file at a time, the moved files stay marked.
+fn record_compaction(entries: &[Entry]) -> Scene {
+    let cell = |pos: usize| (pos / DISK_WIDTH, pos % DISK_WIDTH);
+    // ids wrap around in base 36 to fit in a cell
+    let glyph = |id: usize| char::from_digit((id % 36) as u32, 36).unwrap();
+    let mut disk = Disk::new(entries);
+    let blocks: Vec<_> = disk.iter().collect();
+    let tiles = blocks
+        .chunks(DISK_WIDTH)
+        .map(|row| {
+            row.iter()
+                .map(|block| match block {
+                    Some(id) => Tile::new(glyph(*id), format!("file {id}")),
+                    None => Tile::new('.', "free"),
+                })
+                .collect()
+        })
+        .collect();
+    let mut scene = Scene::new(Grid(tiles));
+    for &Entry { id, data, .. } in entries.iter().rev() {
+        let Some(from) = disk.offset(id) else {
+            continue;
+        };
+        disk = disk.arrange_block(id);
+        let to = disk.offset(id).unwrap_or(from);
+        let step = match to == from {
+            true => scene.push(format!("file {id} stays at {from}")),
+            false => scene.push(format!("file {id} moves from {from} to {to}")),
+        };
+        for i in 0..data {
+            if to != from {
+                step.trail_glyph(cell(from + i), Highlight::Visited, '.')
+                    .trail_glyph(cell(to + i), Highlight::Visited, glyph(id));
+            }
+            step.mark_glyph(cell(to + i), Highlight::Current, glyph(id));
+        }
+    }
+    scene
+}
+
+/// A disk map of `size` files of 1 to 9 blocks, with 0 to 9 free blocks
+/// between them.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut map = String::new();
+    for i in 0..size.max(1) {
+        if i > 0 {
+            map.push(char::from_digit(rng.between(0, 9) as u32, 10).unwrap());
+        }
+        map.push(char::from_digit(rng.between(1, 9) as u32, 10).unwrap());
+    }
+    map.push('\n');
+    map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLES: [(&str, &str); 2] = [("small", "12345"), ("example", "2333133121414131402")];
+
+    #[test]
+    fn disks() {
+        for (name, map) in EXAMPLES {
+            let (entries, _) = parse_input(map).unwrap();
+            let disk = Disk::new(&entries);
+            aoc_core::assert_snapshot!(&format!("day_9_{name}_disk"), disk.to_string());
+            let compacted = compact(&entries, &Cancel::new()).unwrap();
+            aoc_core::assert_snapshot!(&format!("day_9_{name}_compacted"), compacted.to_string());
+        }
+    }
+}
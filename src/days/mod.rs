@@ -0,0 +1,33 @@
+//! The solutions of every day, and the registry the runner picks them from.
+
+use crate::{registry::Registry, solution::Solver};
+
+pub const YEAR: u16 = 2024;
+
+pub mod day_1;
+pub mod day_10;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+pub mod day_9;
+
+pub static SOLUTIONS: &[&dyn Solver] = &[
+    &day_1::Day1,
+    &day_2::Day2,
+    &day_3::Day3,
+    &day_4::Day4,
+    &day_5::Day5,
+    &day_6::Day6,
+    &day_7::Day7,
+    &day_8::Day8,
+    &day_9::Day9,
+    &day_10::Day10,
+];
+
+pub fn registry() -> Registry {
+    Registry::new().with(SOLUTIONS)
+}
@@ -0,0 +1,154 @@
+//! `aoc new <DAY>`: creates a new day from `templates/day.rs.tmpl` and adds it to
+//! the registry in `src/days/mod.rs`.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+const FILE_MARKER: &str = "// @@ ";
+
+/// Splits the template into `(path, contents)` pairs for `day`.
+pub fn render(day: u8) -> Vec<(PathBuf, String)> {
+    let rendered = TEMPLATE.replace("{{day}}", &day.to_string());
+    let mut files: Vec<(PathBuf, String)> = vec![];
+    for line in rendered.lines() {
+        match (line.strip_prefix(FILE_MARKER), files.last_mut()) {
+            (Some(path), _) => files.push((PathBuf::from(path.trim()), String::new())),
+            // the lines before the first marker document the template itself
+            (None, None) => {}
+            (None, Some((_, contents))) => {
+                contents.push_str(line);
+                contents.push('\n');
+            }
+        }
+    }
+    files
+}
+
+/// Adds `pub mod day_N;` and `&day_N::DayN,` to the registry module source.
+pub fn register(registry: &str, day: u8) -> anyhow::Result<String> {
+    let module = format!("pub mod day_{day};");
+    if registry.lines().any(|line| line == module) {
+        bail!("Day {day} is already registered");
+    }
+    let mut lines: Vec<String> = registry.lines().map(str::to_string).collect();
+
+    let modules_start = lines
+        .iter()
+        .position(|line| line.starts_with("pub mod day_"))
+        .context("No `pub mod day_N;` declarations in the registry")?;
+    let modules_end = lines[modules_start..]
+        .iter()
+        .position(|line| !line.starts_with("pub mod day_"))
+        .map_or(lines.len(), |len| modules_start + len);
+    lines.insert(modules_end, module);
+    // keep rustfmt's ordering, which compares the module names
+    lines[modules_start..=modules_end].sort_by_key(|line| line.trim_end_matches(';').to_string());
+
+    let solutions_start = lines
+        .iter()
+        .position(|line| line.starts_with("pub static SOLUTIONS"))
+        .context("No `SOLUTIONS` list in the registry")?;
+    let solutions_end = lines[solutions_start..]
+        .iter()
+        .position(|line| line == "];")
+        .context("The `SOLUTIONS` list is not closed by `];`")?;
+    lines.insert(
+        solutions_start + solutions_end,
+        format!("    &day_{day}::Day{day},"),
+    );
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Writes the files of a new day under `root` (the crate directory) and
+/// registers it. Fails without writing anything if one of the files exists.
+pub fn new_day(root: &Path, day: u8) -> anyhow::Result<Vec<PathBuf>> {
+    let files = render(day);
+    if let Some((path, _)) = files.iter().find(|(path, _)| root.join(path).exists()) {
+        bail!("{} already exists", path.display());
+    }
+    let registry_path = root.join("src/days/mod.rs");
+    let registry = std::fs::read_to_string(&registry_path)
+        .with_context(|| format!("Could not read {}", registry_path.display()))?;
+    let registry = register(&registry, day)?;
+
+    for (path, contents) in &files {
+        std::fs::write(root.join(path), contents)
+            .with_context(|| format!("Could not write {}", path.display()))?;
+    }
+    std::fs::write(&registry_path, registry)
+        .with_context(|| format!("Could not write {}", registry_path.display()))?;
+    Ok(files
+        .into_iter()
+        .map(|(path, _)| path)
+        .chain(Some(PathBuf::from("src/days/mod.rs")))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = include_str!("days/mod.rs");
+
+    #[test]
+    fn renders_a_day_and_its_binary() {
+        let files = render(11);
+        let paths: Vec<_> = files.iter().map(|(path, _)| path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("src/days/day_11.rs"),
+                PathBuf::from("src/bin/day-11.rs")
+            ]
+        );
+        assert!(files
+            .iter()
+            .all(|(_, contents)| !contents.contains("{{day}}")));
+        assert!(files[0].1.contains("pub struct Day11;"));
+    }
+
+    #[test]
+    fn registers_in_rustfmt_order() {
+        let registry = register(REGISTRY, 11).unwrap();
+        let lines: Vec<&str> = registry.lines().collect();
+        let module = lines.iter().position(|&line| line == "pub mod day_11;");
+        assert_eq!(lines[module.unwrap() - 1], "pub mod day_10;");
+        assert_eq!(lines[module.unwrap() + 1], "pub mod day_2;");
+        let solution = lines.iter().position(|&line| line == "    &day_11::Day11,");
+        assert_eq!(lines[solution.unwrap() - 1], "    &day_10::Day10,");
+        assert_eq!(lines[solution.unwrap() + 1], "];");
+    }
+
+    #[test]
+    fn refuses_bad_registries() {
+        let error = |registry: &str, day: u8| register(registry, day).unwrap_err().to_string();
+        assert_eq!(error(REGISTRY, 3), "Day 3 is already registered");
+        assert_eq!(
+            error("pub static SOLUTIONS: &[&dyn Solver] = &[\n];\n", 1),
+            "No `pub mod day_N;` declarations in the registry"
+        );
+        assert_eq!(
+            error("pub mod day_1;\n", 2),
+            "No `SOLUTIONS` list in the registry"
+        );
+    }
+
+    #[test]
+    fn new_day_keeps_existing_files() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("src/days")).unwrap();
+        std::fs::create_dir_all(root.join("src/bin")).unwrap();
+        std::fs::write(root.join("src/days/mod.rs"), REGISTRY).unwrap();
+        let written = new_day(&root, 11).unwrap();
+        assert_eq!(written.len(), 3);
+        assert!(root.join("src/bin/day-11.rs").exists());
+        let registry = std::fs::read_to_string(root.join("src/days/mod.rs")).unwrap();
+        assert!(registry.contains("pub mod day_11;"));
+        let error = new_day(&root, 11).unwrap_err().to_string();
+        assert_eq!(error, "src/days/day_11.rs already exists");
+    }
+}
@@ -0,0 +1,101 @@
+//! Every day against the naive solvers in [`reference`], on inputs from its
+//! generator. An input is cut into records (lines, grid rows, updates, disk map
+//! pairs) so that a failing case shrinks by dropping records until none can go.
+
+mod reference;
+
+use aoc2024::{
+    days::{day_1, day_10, day_2, day_3, day_4, day_5, day_6, day_7, day_8, day_9},
+    generate::Rng,
+    solution::solve,
+    Part, Solution,
+};
+use proptest::{prelude::*, sample::subsequence};
+
+type Reference = fn(&str) -> Option<u64>;
+
+/// How an input splits into a head that always stays and records that can be
+/// dropped.
+#[derive(Debug, Clone, Copy)]
+enum Records {
+    Lines,
+    /// The page ordering rules stay, the updates go.
+    Updates,
+    /// The first file stays, the free space and file pairs after it go.
+    DiskMap,
+}
+
+impl Records {
+    fn split(self, input: &str) -> (String, Vec<String>) {
+        match self {
+            Records::Lines => (
+                String::new(),
+                input.lines().map(|l| format!("{l}\n")).collect(),
+            ),
+            Records::Updates => {
+                let (rules, updates) = input.split_once("\n\n").unwrap();
+                let updates = updates.lines().map(|l| format!("{l}\n")).collect();
+                (format!("{rules}\n\n"), updates)
+            }
+            Records::DiskMap => {
+                let map = input.trim();
+                let pairs = map.as_bytes()[1..].chunks(2);
+                let pairs = pairs.map(|pair| String::from_utf8(pair.to_vec()).unwrap());
+                (map[..1].to_string(), pairs.collect())
+            }
+        }
+    }
+}
+
+/// Inputs of `S` up to `max_size`, shrinking towards fewer records.
+fn inputs<S: Solution>(records: Records, max_size: usize) -> impl Strategy<Value = String> {
+    (any::<u64>(), 1..=max_size).prop_flat_map(move |(seed, size)| {
+        let input = S::generate(&mut Rng::new(seed), size).expect("every day has a generator");
+        let (head, records) = records.split(&input);
+        // at least one record, an empty input is not a puzzle
+        let count = records.len();
+        subsequence(records, count.min(1)..=count)
+            .prop_map(move |records| head.clone() + &records.concat())
+    })
+}
+
+fn check<S: Solution>(input: &str, part: Part, reference: Reference) -> Result<(), TestCaseError> {
+    prop_assume!(S::validate(input).is_empty());
+    let expected = reference(input).map(|n| n.to_string());
+    let answer = solve::<S>(input, part).ok().map(|answer| answer.0);
+    prop_assert_eq!(answer, expected, "part {} of\n{}", part.number(), input);
+    Ok(())
+}
+
+macro_rules! differential {
+    ($name:ident, $day:ident :: $solution:ident, $records:expr, $max_size:expr) => {
+        mod $name {
+            use super::*;
+
+            proptest! {
+                #![proptest_config(ProptestConfig::with_cases(1000))]
+
+                #[test]
+                fn part_1(input in inputs::<$day::$solution>($records, $max_size)) {
+                    check::<$day::$solution>(&input, Part::One, reference::$day::part_1)?;
+                }
+
+                #[test]
+                fn part_2(input in inputs::<$day::$solution>($records, $max_size)) {
+                    check::<$day::$solution>(&input, Part::Two, reference::$day::part_2)?;
+                }
+            }
+        }
+    };
+}
+
+differential!(day_1_tests, day_1::Day1, Records::Lines, 20);
+differential!(day_2_tests, day_2::Day2, Records::Lines, 20);
+differential!(day_3_tests, day_3::Day3, Records::Lines, 150);
+differential!(day_4_tests, day_4::Day4, Records::Lines, 8);
+differential!(day_5_tests, day_5::Day5, Records::Updates, 8);
+differential!(day_6_tests, day_6::Day6, Records::Lines, 8);
+differential!(day_7_tests, day_7::Day7, Records::Lines, 10);
+differential!(day_8_tests, day_8::Day8, Records::Lines, 12);
+differential!(day_9_tests, day_9::Day9, Records::DiskMap, 20);
+differential!(day_10_tests, day_10::Day10, Records::Lines, 8);
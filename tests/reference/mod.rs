@@ -0,0 +1,498 @@
+//! Naive solvers, written straight from the puzzle text with no cleverness, to
+//! check the real ones against. They work on the raw input and return `None`
+//! where the real solver fails: a guard that never leaves, no `mul` at all.
+
+type Grid = Vec<Vec<char>>;
+
+fn grid(input: &str) -> Grid {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+fn numbers<T: std::str::FromStr>(line: &str, separator: char) -> Vec<T> {
+    line.split(separator)
+        .filter_map(|number| number.trim().parse().ok())
+        .collect()
+}
+
+pub mod day_1 {
+    fn lists(input: &str) -> (Vec<u64>, Vec<u64>) {
+        input
+            .lines()
+            .map(|line| {
+                let pair: Vec<u64> = line
+                    .split_whitespace()
+                    .map(|n| n.parse().unwrap())
+                    .collect();
+                (pair[0], pair[1])
+            })
+            .unzip()
+    }
+
+    pub fn part_1(input: &str) -> Option<u64> {
+        let (mut left, mut right) = lists(input);
+        left.sort();
+        right.sort();
+        Some(left.iter().zip(&right).map(|(l, r)| l.abs_diff(*r)).sum())
+    }
+
+    pub fn part_2(input: &str) -> Option<u64> {
+        let (left, right) = lists(input);
+        let similarity = |l: &u64| l * right.iter().filter(|r| *r == l).count() as u64;
+        Some(left.iter().map(similarity).sum())
+    }
+}
+
+pub mod day_2 {
+    fn safe(report: &[i64]) -> bool {
+        let diffs: Vec<i64> = report.windows(2).map(|w| w[1] - w[0]).collect();
+        diffs.iter().all(|d| (1..=3).contains(d)) || diffs.iter().all(|d| (-3..=-1).contains(d))
+    }
+
+    fn reports(input: &str) -> Vec<Vec<i64>> {
+        input
+            .lines()
+            .map(|line| super::numbers(line, ' '))
+            .collect()
+    }
+
+    pub fn part_1(input: &str) -> Option<u64> {
+        Some(reports(input).iter().filter(|r| safe(r)).count() as u64)
+    }
+
+    /// Tries removing every level in turn.
+    pub fn part_2(input: &str) -> Option<u64> {
+        let dampened = |report: &Vec<i64>| {
+            (0..report.len()).any(|i| {
+                let mut report = report.clone();
+                report.remove(i);
+                safe(&report)
+            })
+        };
+        let reports = reports(input);
+        Some(reports.iter().filter(|r| safe(r) || dampened(r)).count() as u64)
+    }
+}
+
+pub mod day_3 {
+    /// `mul(X,Y)` at the start of `s`, with X and Y of 1 to 3 digits.
+    fn mul(s: &str) -> Option<u64> {
+        let rest = s.strip_prefix("mul(")?;
+        let (a, rest) = rest.split_once(',')?;
+        let (b, _) = rest.split_once(')')?;
+        let number = |n: &str| {
+            let valid = (1..=3).contains(&n.len()) && n.bytes().all(|b| b.is_ascii_digit());
+            valid.then(|| n.parse::<u64>().unwrap())
+        };
+        Some(number(a)? * number(b)?)
+    }
+
+    pub fn part_1(input: &str) -> Option<u64> {
+        let muls: Vec<u64> = (0..input.len()).filter_map(|i| mul(&input[i..])).collect();
+        (!muls.is_empty()).then(|| muls.iter().sum())
+    }
+
+    pub fn part_2(input: &str) -> Option<u64> {
+        let mut enabled = true;
+        let mut sum = 0;
+        let mut found = false;
+        for i in 0..input.len() {
+            let s = &input[i..];
+            if s.starts_with("do()") {
+                (enabled, found) = (true, true);
+            } else if s.starts_with("don't()") {
+                (enabled, found) = (false, true);
+            } else if let Some(product) = mul(s) {
+                found = true;
+                if enabled {
+                    sum += product;
+                }
+            }
+        }
+        found.then_some(sum)
+    }
+}
+
+pub mod day_4 {
+    use super::Grid;
+
+    fn at(grid: &Grid, y: isize, x: isize) -> Option<char> {
+        let row = grid.get(usize::try_from(y).ok()?)?;
+        row.get(usize::try_from(x).ok()?).copied()
+    }
+
+    pub fn part_1(input: &str) -> Option<u64> {
+        let grid = super::grid(input);
+        let mut count = 0;
+        for y in 0..grid.len() as isize {
+            for x in 0..grid[y as usize].len() as isize {
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        let word: String = (0..4)
+                            .filter_map(|i| at(&grid, y + i * dy, x + i * dx))
+                            .collect();
+                        if (dy, dx) != (0, 0) && word == "XMAS" {
+                            count += 1;
+                        }
+                    }
+                }
+            }
+        }
+        Some(count)
+    }
+
+    pub fn part_2(input: &str) -> Option<u64> {
+        let grid = super::grid(input);
+        let mut count = 0;
+        for y in 0..grid.len() as isize {
+            for x in 0..grid[y as usize].len() as isize {
+                let diagonal = |d: isize| -> Option<String> {
+                    [
+                        at(&grid, y - 1, x - d),
+                        at(&grid, y, x),
+                        at(&grid, y + 1, x + d),
+                    ]
+                    .into_iter()
+                    .collect()
+                };
+                let mas = |word: Option<String>| matches!(word.as_deref(), Some("MAS" | "SAM"));
+                if mas(diagonal(1)) && mas(diagonal(-1)) {
+                    count += 1;
+                }
+            }
+        }
+        Some(count)
+    }
+}
+
+pub mod day_5 {
+    type Rules = Vec<(u64, u64)>;
+
+    fn parse(input: &str) -> (Rules, Vec<Vec<u64>>) {
+        let (rules, updates) = input.split_once("\n\n").unwrap();
+        let rules = rules
+            .lines()
+            .map(|line| {
+                let (a, b) = line.split_once('|').unwrap();
+                (a.parse().unwrap(), b.parse().unwrap())
+            })
+            .collect();
+        let updates = updates
+            .lines()
+            .map(|line| super::numbers(line, ','))
+            .collect();
+        (rules, updates)
+    }
+
+    /// The first pair of pages that breaks a rule.
+    fn broken(rules: &Rules, update: &[u64]) -> Option<(usize, usize)> {
+        (0..update.len())
+            .flat_map(|i| (i + 1..update.len()).map(move |j| (i, j)))
+            .find(|&(i, j)| rules.contains(&(update[j], update[i])))
+    }
+
+    pub fn part_1(input: &str) -> Option<u64> {
+        let (rules, updates) = parse(input);
+        let ordered = updates.iter().filter(|u| broken(&rules, u).is_none());
+        Some(ordered.map(|u| u[u.len() / 2]).sum())
+    }
+
+    /// Swaps pages that break a rule until none do.
+    pub fn part_2(input: &str) -> Option<u64> {
+        let (rules, updates) = parse(input);
+        let mut sum = 0;
+        for mut update in updates {
+            if broken(&rules, &update).is_none() {
+                continue;
+            }
+            while let Some((i, j)) = broken(&rules, &update) {
+                update.swap(i, j);
+            }
+            sum += update[update.len() / 2];
+        }
+        Some(sum)
+    }
+}
+
+pub mod day_6 {
+    use std::collections::HashSet;
+
+    use super::Grid;
+
+    /// The cells the guard walks through, `None` when the guard never leaves.
+    fn walk(grid: &Grid) -> Option<HashSet<(isize, isize)>> {
+        let (mut y, mut x) = (0..grid.len())
+            .flat_map(|y| (0..grid[y].len()).map(move |x| (y, x)))
+            .find(|&(y, x)| grid[y][x] == '^')
+            .map(|(y, x)| (y as isize, x as isize))?;
+        let (mut dy, mut dx) = (-1, 0);
+        let mut seen = HashSet::new();
+        let cell = |y: isize, x: isize| {
+            let row = grid.get(usize::try_from(y).ok()?)?;
+            row.get(usize::try_from(x).ok()?).copied()
+        };
+        while seen.insert((y, x, dy, dx)) {
+            match cell(y + dy, x + dx) {
+                None => return Some(seen.iter().map(|&(y, x, _, _)| (y, x)).collect()),
+                Some('#') => (dy, dx) = (dx, -dy),
+                Some(_) => (y, x) = (y + dy, x + dx),
+            }
+        }
+        None
+    }
+
+    /// Only handles guards facing up, the generator also makes guards facing
+    /// elsewhere, which are turned up along with the grid.
+    fn upright(input: &str) -> Grid {
+        let mut grid = super::grid(input);
+        let facing = grid
+            .iter()
+            .flatten()
+            .find(|c| "^>v<".contains(**c))
+            .copied();
+        let turns = "^<v>".find(facing.unwrap_or('^')).unwrap();
+        for _ in 0..turns {
+            // rotate clockwise, a guard facing left ends up facing up
+            let rows = grid.len();
+            let columns = grid.first().map_or(0, Vec::len);
+            grid = (0..columns)
+                .map(|x| (0..rows).rev().map(|y| grid[y][x]).collect())
+                .collect();
+        }
+        for c in grid.iter_mut().flatten() {
+            if "^>v<".contains(*c) {
+                *c = '^';
+            }
+        }
+        grid
+    }
+
+    pub fn part_1(input: &str) -> Option<u64> {
+        walk(&upright(input)).map(|cells| cells.len() as u64)
+    }
+
+    /// Tries an obstacle on every free cell.
+    pub fn part_2(input: &str) -> Option<u64> {
+        let grid = upright(input);
+        walk(&grid)?;
+        let mut loops = 0;
+        for y in 0..grid.len() {
+            for x in 0..grid[y].len() {
+                if grid[y][x] == '.' {
+                    let mut blocked = grid.clone();
+                    blocked[y][x] = '#';
+                    loops += walk(&blocked).is_none() as u64;
+                }
+            }
+        }
+        Some(loops)
+    }
+}
+
+pub mod day_7 {
+    fn equations(input: &str) -> Vec<(u64, Vec<u64>)> {
+        input
+            .lines()
+            .map(|line| {
+                let (result, numbers) = line.split_once(": ").unwrap();
+                (result.parse().unwrap(), super::numbers(numbers, ' '))
+            })
+            .collect()
+    }
+
+    /// Tries every combination of operators, counting in base `operators`.
+    fn solvable(result: u64, numbers: &[u64], operators: u32) -> bool {
+        let slots = numbers.len() as u32 - 1;
+        (0..operators.pow(slots)).any(|mut combination| {
+            let value = numbers[1..].iter().try_fold(numbers[0], |acc, &n| {
+                let operator = combination % operators;
+                combination /= operators;
+                match operator {
+                    0 => acc.checked_add(n),
+                    1 => acc.checked_mul(n),
+                    _ => format!("{acc}{n}").parse().ok(),
+                }
+            });
+            value == Some(result)
+        })
+    }
+
+    fn total(input: &str, operators: u32) -> Option<u64> {
+        let equations = equations(input);
+        let solved = equations
+            .iter()
+            .filter(|(result, numbers)| solvable(*result, numbers, operators));
+        Some(solved.map(|(result, _)| result).sum())
+    }
+
+    pub fn part_1(input: &str) -> Option<u64> {
+        total(input, 2)
+    }
+
+    pub fn part_2(input: &str) -> Option<u64> {
+        total(input, 3)
+    }
+}
+
+pub mod day_8 {
+    use std::collections::HashSet;
+
+    fn gcd(a: isize, b: isize) -> isize {
+        match b {
+            0 => a.abs(),
+            _ => gcd(b, a % b),
+        }
+    }
+
+    /// Every cell checked against every pair of antennas of a frequency.
+    fn antinodes(
+        input: &str,
+        in_line: impl Fn((isize, isize), (isize, isize), (isize, isize)) -> bool,
+    ) -> u64 {
+        let grid = super::grid(input);
+        let antennas: Vec<(char, (isize, isize))> = (0..grid.len())
+            .flat_map(|y| (0..grid[y].len()).map(move |x| (y, x)))
+            .filter(|&(y, x)| grid[y][x] != '.')
+            .map(|(y, x)| (grid[y][x], (y as isize, x as isize)))
+            .collect();
+        let mut antinodes = HashSet::new();
+        for y in 0..grid.len() as isize {
+            for x in 0..grid[y as usize].len() as isize {
+                for (i, &(f, a)) in antennas.iter().enumerate() {
+                    for &(g, b) in &antennas[i + 1..] {
+                        if f == g && in_line((y, x), a, b) {
+                            antinodes.insert((y, x));
+                        }
+                    }
+                }
+            }
+        }
+        antinodes.len() as u64
+    }
+
+    /// Cells in line with both antennas and twice as far from one as from the
+    /// other.
+    pub fn part_1(input: &str) -> Option<u64> {
+        Some(antinodes(input, |p, a, b| {
+            let twice = |a: (isize, isize), b: (isize, isize)| (2 * b.0 - a.0, 2 * b.1 - a.1);
+            p == twice(a, b) || p == twice(b, a)
+        }))
+    }
+
+    /// Grid cells exactly in line with both antennas.
+    pub fn part_2(input: &str) -> Option<u64> {
+        Some(antinodes(input, |p, a, b| {
+            let (dy, dx) = (b.0 - a.0, b.1 - a.1);
+            let g = gcd(dy, dx);
+            // with the gcd out, every cell on the line is a whole number of steps away
+            let (dy, dx) = (dy / g, dx / g);
+            (p.0 - a.0) * dx == (p.1 - a.1) * dy
+        }))
+    }
+}
+
+pub mod day_9 {
+    /// The disk one block at a time, `None` for free blocks.
+    fn blocks(input: &str) -> Vec<Option<u64>> {
+        let mut blocks = vec![];
+        for (i, c) in input.trim().chars().enumerate() {
+            let id = (i % 2 == 0).then_some(i as u64 / 2);
+            blocks.extend(std::iter::repeat_n(id, c.to_digit(10).unwrap() as usize));
+        }
+        blocks
+    }
+
+    fn checksum(blocks: &[Option<u64>]) -> u64 {
+        let ids = blocks.iter().enumerate();
+        ids.map(|(i, id)| i as u64 * id.unwrap_or(0)).sum()
+    }
+
+    /// Moves the last block to the first free block until there are no gaps.
+    pub fn part_1(input: &str) -> Option<u64> {
+        let mut blocks = blocks(input);
+        loop {
+            while blocks.last() == Some(&None) {
+                blocks.pop();
+            }
+            let Some(free) = blocks.iter().position(Option::is_none) else {
+                return Some(checksum(&blocks));
+            };
+            blocks[free] = blocks.pop().unwrap();
+        }
+    }
+
+    /// Moves whole files, highest id first, to the leftmost gap that fits.
+    pub fn part_2(input: &str) -> Option<u64> {
+        let mut blocks = blocks(input);
+        let last = blocks.iter().flatten().max().copied().unwrap_or(0);
+        for id in (0..=last).rev() {
+            let Some(start) = blocks.iter().position(|&b| b == Some(id)) else {
+                continue;
+            };
+            let len = blocks[start..]
+                .iter()
+                .take_while(|&&b| b == Some(id))
+                .count();
+            let gap = (0..start).find(|&i| blocks[i..i + len].iter().all(Option::is_none));
+            if let Some(gap) = gap {
+                for i in 0..len {
+                    blocks.swap(gap + i, start + i);
+                }
+            }
+        }
+        Some(checksum(&blocks))
+    }
+}
+
+pub mod day_10 {
+    use std::collections::HashSet;
+
+    use super::Grid;
+
+    /// Every hiking trail from `(y, x)`, as the peaks they end on.
+    fn trails(grid: &Grid, y: usize, x: usize, peaks: &mut Vec<(usize, usize)>) {
+        let height = grid[y][x].to_digit(10).unwrap();
+        if height == 9 {
+            peaks.push((y, x));
+            return;
+        }
+        let neighbours = [
+            (y.wrapping_sub(1), x),
+            (y + 1, x),
+            (y, x.wrapping_sub(1)),
+            (y, x + 1),
+        ];
+        for (ny, nx) in neighbours {
+            let next = grid.get(ny).and_then(|row| row.get(nx));
+            if next.and_then(|c| c.to_digit(10)) == Some(height + 1) {
+                trails(grid, ny, nx, peaks);
+            }
+        }
+    }
+
+    fn trailheads(input: &str) -> Vec<Vec<(usize, usize)>> {
+        let grid = super::grid(input);
+        let mut heads = vec![];
+        for y in 0..grid.len() {
+            for x in 0..grid[y].len() {
+                if grid[y][x] == '0' {
+                    let mut peaks = vec![];
+                    trails(&grid, y, x, &mut peaks);
+                    heads.push(peaks);
+                }
+            }
+        }
+        heads
+    }
+
+    pub fn part_1(input: &str) -> Option<u64> {
+        let heads = trailheads(input);
+        let distinct = heads
+            .iter()
+            .map(|peaks| peaks.iter().collect::<HashSet<_>>().len());
+        Some(distinct.sum::<usize>() as u64)
+    }
+
+    pub fn part_2(input: &str) -> Option<u64> {
+        Some(trailheads(input).iter().map(Vec::len).sum::<usize>() as u64)
+    }
+}
@@ -0,0 +1,138 @@
+//! Every day's diagnostics, with the line and column they point at.
+
+use aoc2024::{
+    days::{day_1, day_10, day_2, day_3, day_4, day_5, day_6, day_8, day_9},
+    solution::solve,
+    Part, Solution,
+};
+
+fn diagnostics<S: Solution>(input: &str) -> Vec<String> {
+    S::validate(input).iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn day_1() {
+    assert!(diagnostics::<day_1::Day1>("3   4\n4   3\n").is_empty());
+    assert_eq!(
+        diagnostics::<day_1::Day1>("3   4\n4   3   9\n5\n"),
+        [
+            "2:1: expected 2 numbers, found 3",
+            "3:1: expected 2 numbers, found 1",
+        ]
+    );
+    assert_eq!(
+        diagnostics::<day_1::Day1>("3   -4\n"),
+        ["1:5: \"-4\" is not a number"]
+    );
+}
+
+#[test]
+fn day_2() {
+    assert!(diagnostics::<day_2::Day2>("7 6 4 2 1\n1 2 7 8 9\n").is_empty());
+    assert_eq!(
+        diagnostics::<day_2::Day2>("7 6 x 2\n\n1  3 9999999999\n"),
+        [
+            "1:5: \"x\" is not a number",
+            "2:1: empty report",
+            "3:3: \"\" is not a number",
+            "3:6: \"9999999999\" is not a number",
+        ]
+    );
+}
+
+#[test]
+fn day_2_parse_keeps_malformed_levels_out() {
+    assert!(solve::<day_2::Day2>("1 2 x\n", Part::One).is_err());
+}
+
+#[test]
+fn day_3() {
+    assert!(diagnostics::<day_3::Day3>("xmul(2,4)%&mul[3,7]").is_empty());
+    assert_eq!(
+        diagnostics::<day_3::Day3>("mul(2,4"),
+        ["1:1: no `mul(X,Y)` instructions"]
+    );
+    assert_eq!(
+        diagnostics::<day_3::Day3>(""),
+        ["1:1: no `mul(X,Y)` instructions"]
+    );
+}
+
+#[test]
+fn day_4() {
+    assert!(diagnostics::<day_4::Day4>("XMAS\nSAMX\n").is_empty());
+    assert_eq!(
+        diagnostics::<day_4::Day4>("XMAS\nSAM\nXMAZ\n"),
+        ["2:4: expected 4 columns, found 3", "3:4: unexpected 'Z'"]
+    );
+}
+
+#[test]
+fn day_5() {
+    assert!(diagnostics::<day_5::Day5>("47|53\n\n75,47,53\n").is_empty());
+    // the updates without the rules above them
+    assert_eq!(
+        diagnostics::<day_5::Day5>("75,47\n"),
+        [
+            "1:1: expected a rule like `47|53`",
+            "1:1: \"75,47\" is not a number",
+            "2:1: missing the blank line between rules and updates",
+        ]
+    );
+    assert_eq!(
+        diagnostics::<day_5::Day5>("47|x\n\n75,47\n\n"),
+        [
+            "1:4: \"x\" is not a number",
+            "4:1: extra blank line, the first one is on line 2",
+        ]
+    );
+}
+
+#[test]
+fn day_6() {
+    assert!(diagnostics::<day_6::Day6>("..#\n.^.\n").is_empty());
+    assert_eq!(
+        diagnostics::<day_6::Day6>("..#\n...\n"),
+        ["1:1: no guard in the map"]
+    );
+    assert_eq!(
+        diagnostics::<day_6::Day6>(">.#\n.^.\n..<\n"),
+        [
+            "2:2: 3 guards in the map, expected 1",
+            "3:3: 3 guards in the map, expected 1",
+        ]
+    );
+}
+
+#[test]
+fn day_8() {
+    assert!(diagnostics::<day_8::Day8>("..a.\n.A0.\n").is_empty());
+    assert_eq!(
+        diagnostics::<day_8::Day8>("..a.\n.#..\n"),
+        ["2:2: unexpected '#'"]
+    );
+    assert_eq!(diagnostics::<day_8::Day8>(""), ["1:1: empty input"]);
+}
+
+#[test]
+fn day_9() {
+    assert!(diagnostics::<day_9::Day9>("2333133121414131402\n").is_empty());
+    assert_eq!(
+        diagnostics::<day_9::Day9>("23x3 1\n\n42\n"),
+        [
+            "1:3: unexpected 'x'",
+            "1:5: unexpected ' '",
+            "3:1: the disk map is a single line",
+        ]
+    );
+    assert_eq!(diagnostics::<day_9::Day9>("\n"), ["1:1: empty disk map"]);
+}
+
+#[test]
+fn day_10() {
+    assert!(diagnostics::<day_10::Day10>("0123\n1234\n").is_empty());
+    assert_eq!(
+        diagnostics::<day_10::Day10>("0123\n1.34\n"),
+        ["2:2: unexpected '.'"]
+    );
+}